@@ -0,0 +1,82 @@
+/*
+ * PlenumNET THDL C API
+ *
+ * Embeds the ternary hardware synthesizer in C/C++ tools.
+ * Link against libplenumnet_thdl_capi (shared or static).
+ *
+ * Copyright (c) 2026 Capomastro Holdings Ltd. All rights reserved.
+ */
+
+#ifndef PLENUMNET_THDL_H
+#define PLENUMNET_THDL_H
+
+#include <stdbool.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum ThdlStatus {
+    THDL_OK = 0,
+    THDL_NULL_POINTER = 1,
+    THDL_INVALID_UTF8 = 2,
+    THDL_INVALID_ARGUMENT = 3,
+    THDL_PARSE_ERROR = 4,
+    THDL_OPTIMIZATION_ERROR = 5,
+    THDL_GENERATION_ERROR = 6,
+    THDL_TIMING_VIOLATION = 7,
+    THDL_PANIC = 8
+} ThdlStatus;
+
+typedef enum ThdlTarget {
+    THDL_TARGET_XILINX_FPGA = 0,
+    THDL_TARGET_INTEL_FPGA = 1,
+    THDL_TARGET_LATTICE_FPGA = 2,
+    THDL_TARGET_ASIC = 3,
+    THDL_TARGET_SIMULATION = 4
+} ThdlTarget;
+
+typedef struct ThdlStats {
+    uint64_t trit_cells;
+    uint64_t gates;
+    uint64_t flip_flops;
+    double estimated_area_um2;
+    double estimated_power_mw;
+    uint64_t critical_path_ps;
+} ThdlStats;
+
+/* Opaque handles */
+typedef struct ThdlOptions ThdlOptions;
+typedef struct ThdlResult ThdlResult;
+
+const char *thdl_version(void);
+
+/* Message for the last failure on the calling thread, or NULL. */
+const char *thdl_last_error_message(void);
+
+ThdlOptions *thdl_options_new(void);
+void thdl_options_free(ThdlOptions *options);
+/* target is a ThdlTarget value; anything else returns THDL_INVALID_ARGUMENT. */
+ThdlStatus thdl_options_set_target(ThdlOptions *options, uint32_t target);
+ThdlStatus thdl_options_set_optimizations(ThdlOptions *options, bool optimize_area,
+                                          bool optimize_speed, bool optimize_power);
+/* Margins that do not fit in the period make thdl_synthesize return
+ * THDL_TIMING_VIOLATION. */
+ThdlStatus thdl_options_set_timing(ThdlOptions *options, uint64_t max_clock_period_ps,
+                                   uint64_t setup_margin_ps, uint64_t hold_margin_ps);
+
+/* options may be NULL for defaults; *out_result is NULL on failure. */
+ThdlStatus thdl_synthesize(const char *source, const ThdlOptions *options,
+                           ThdlResult **out_result);
+
+/* Owned by the result; valid until thdl_result_free. */
+const char *thdl_result_output(const ThdlResult *result);
+ThdlStatus thdl_result_stats(const ThdlResult *result, ThdlStats *out_stats);
+void thdl_result_free(ThdlResult *result);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* PLENUMNET_THDL_H */
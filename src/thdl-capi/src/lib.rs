@@ -0,0 +1,358 @@
+//! PlenumNET THDL C API
+//!
+//! `extern "C"` wrapper around [`plenumnet_thdl::synthesize`] so EDA tools
+//! written in C/C++ can embed the ternary synthesizer. Options and results
+//! are opaque handles; every fallible call returns a [`ThdlStatus`] and
+//! records a message readable through [`thdl_last_error_message`].
+//!
+//! The matching declarations are in `include/thdl.h`.
+//!
+//! # Copyright
+//! Copyright (c) 2026 Capomastro Holdings Ltd. All rights reserved.
+
+use plenumnet_thdl::{SynthesisError, SynthesisOptions, SynthesisResult, Target};
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+/// Status codes returned by fallible calls
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThdlStatus {
+    Ok = 0,
+    NullPointer = 1,
+    InvalidUtf8 = 2,
+    InvalidArgument = 3,
+    ParseError = 4,
+    OptimizationError = 5,
+    GenerationError = 6,
+    TimingViolation = 7,
+    Panic = 8,
+}
+
+/// Synthesis targets (mirrors [`Target`])
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThdlTarget {
+    XilinxFpga = 0,
+    IntelFpga = 1,
+    LatticeFpga = 2,
+    Asic = 3,
+    Simulation = 4,
+}
+
+impl From<ThdlTarget> for Target {
+    fn from(target: ThdlTarget) -> Self {
+        match target {
+            ThdlTarget::XilinxFpga => Target::XilinxFpga,
+            ThdlTarget::IntelFpga => Target::IntelFpga,
+            ThdlTarget::LatticeFpga => Target::LatticeFpga,
+            ThdlTarget::Asic => Target::Asic,
+            ThdlTarget::Simulation => Target::Simulation,
+        }
+    }
+}
+
+impl TryFrom<u32> for ThdlTarget {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(ThdlTarget::XilinxFpga),
+            1 => Ok(ThdlTarget::IntelFpga),
+            2 => Ok(ThdlTarget::LatticeFpga),
+            3 => Ok(ThdlTarget::Asic),
+            4 => Ok(ThdlTarget::Simulation),
+            other => Err(other),
+        }
+    }
+}
+
+/// Synthesis statistics (mirrors `SynthesisStats`)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ThdlStats {
+    pub trit_cells: u64,
+    pub gates: u64,
+    pub flip_flops: u64,
+    pub estimated_area_um2: f64,
+    pub estimated_power_mw: f64,
+    pub critical_path_ps: u64,
+}
+
+/// Opaque synthesis options handle
+pub struct ThdlOptions {
+    inner: SynthesisOptions,
+}
+
+/// Opaque synthesis result handle
+pub struct ThdlResult {
+    inner: SynthesisResult,
+    output: CString,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: &str) {
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|e| *e.borrow_mut() = Some(message));
+}
+
+fn fail(status: ThdlStatus, message: &str) -> ThdlStatus {
+    set_last_error(message);
+    status
+}
+
+fn status_for(error: &SynthesisError) -> ThdlStatus {
+    match error {
+        SynthesisError::ParseError(_) => ThdlStatus::ParseError,
+        SynthesisError::OptimizationError(_) => ThdlStatus::OptimizationError,
+        SynthesisError::GenerationError(_) => ThdlStatus::GenerationError,
+        SynthesisError::TimingViolation { .. } => ThdlStatus::TimingViolation,
+    }
+}
+
+/// Library version as a static NUL-terminated string
+#[no_mangle]
+pub extern "C" fn thdl_version() -> *const c_char {
+    static VERSION: &str = concat!(env!("CARGO_PKG_VERSION"), "\0");
+    VERSION.as_ptr() as *const c_char
+}
+
+/// Message for the most recent failure on this thread, or NULL
+///
+/// The pointer stays valid until the next failing call on the same thread.
+#[no_mangle]
+pub extern "C" fn thdl_last_error_message() -> *const c_char {
+    LAST_ERROR.with(|e| e.borrow().as_ref().map(|m| m.as_ptr()).unwrap_or(ptr::null()))
+}
+
+/// Create options with the library defaults; free with [`thdl_options_free`]
+#[no_mangle]
+pub extern "C" fn thdl_options_new() -> *mut ThdlOptions {
+    Box::into_raw(Box::new(ThdlOptions {
+        inner: SynthesisOptions::default(),
+    }))
+}
+
+/// Free an options handle (NULL is ignored)
+///
+/// # Safety
+/// `options` must be NULL or a handle from [`thdl_options_new`] not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn thdl_options_free(options: *mut ThdlOptions) {
+    if !options.is_null() {
+        drop(Box::from_raw(options));
+    }
+}
+
+/// Select the synthesis target (a [`ThdlTarget`] value)
+///
+/// The target is taken as a plain integer so out-of-range values from C
+/// are rejected with `InvalidArgument` instead of forming an invalid enum.
+///
+/// # Safety
+/// `options` must be NULL or a live handle from [`thdl_options_new`].
+#[no_mangle]
+pub unsafe extern "C" fn thdl_options_set_target(options: *mut ThdlOptions, target: u32) -> ThdlStatus {
+    let Some(options) = options.as_mut() else {
+        return fail(ThdlStatus::NullPointer, "options is NULL");
+    };
+    match ThdlTarget::try_from(target) {
+        Ok(target) => {
+            options.inner.target = target.into();
+            ThdlStatus::Ok
+        }
+        Err(value) => fail(ThdlStatus::InvalidArgument, &format!("unknown target {}", value)),
+    }
+}
+
+/// Enable or disable the area/speed/power optimization passes
+///
+/// # Safety
+/// `options` must be NULL or a live handle from [`thdl_options_new`].
+#[no_mangle]
+pub unsafe extern "C" fn thdl_options_set_optimizations(
+    options: *mut ThdlOptions,
+    optimize_area: bool,
+    optimize_speed: bool,
+    optimize_power: bool,
+) -> ThdlStatus {
+    match options.as_mut() {
+        Some(options) => {
+            options.inner.optimize_area = optimize_area;
+            options.inner.optimize_speed = optimize_speed;
+            options.inner.optimize_power = optimize_power;
+            ThdlStatus::Ok
+        }
+        None => fail(ThdlStatus::NullPointer, "options is NULL"),
+    }
+}
+
+/// Set timing constraints (all values in picoseconds)
+///
+/// Margins that do not fit in the clock period are reported by
+/// [`thdl_synthesize`] as `TimingViolation`.
+///
+/// # Safety
+/// `options` must be NULL or a live handle from [`thdl_options_new`].
+#[no_mangle]
+pub unsafe extern "C" fn thdl_options_set_timing(
+    options: *mut ThdlOptions,
+    max_clock_period_ps: u64,
+    setup_margin_ps: u64,
+    hold_margin_ps: u64,
+) -> ThdlStatus {
+    let Some(options) = options.as_mut() else {
+        return fail(ThdlStatus::NullPointer, "options is NULL");
+    };
+    let timing = &mut options.inner.timing_constraints;
+    timing.max_clock_period_ps = max_clock_period_ps;
+    timing.setup_margin_ps = setup_margin_ps;
+    timing.hold_margin_ps = hold_margin_ps;
+    ThdlStatus::Ok
+}
+
+/// Synthesize THDL source
+///
+/// On success `*out_result` receives a handle to free with
+/// [`thdl_result_free`]; on failure it is set to NULL. `options` may be
+/// NULL to use the defaults.
+///
+/// # Safety
+/// `source` must be a NUL-terminated string, `options` NULL or a live
+/// options handle, and `out_result` a valid pointer to writable storage.
+#[no_mangle]
+pub unsafe extern "C" fn thdl_synthesize(
+    source: *const c_char,
+    options: *const ThdlOptions,
+    out_result: *mut *mut ThdlResult,
+) -> ThdlStatus {
+    if out_result.is_null() {
+        return fail(ThdlStatus::NullPointer, "out_result is NULL");
+    }
+    *out_result = ptr::null_mut();
+
+    if source.is_null() {
+        return fail(ThdlStatus::NullPointer, "source is NULL");
+    }
+    let source = match CStr::from_ptr(source).to_str() {
+        Ok(s) => s,
+        Err(_) => return fail(ThdlStatus::InvalidUtf8, "source is not valid UTF-8"),
+    };
+    let default_options = SynthesisOptions::default();
+    let options = options.as_ref().map(|o| &o.inner).unwrap_or(&default_options);
+
+    // Panics must not unwind across the C boundary
+    let outcome = catch_unwind(AssertUnwindSafe(|| plenumnet_thdl::synthesize(source, options)));
+
+    match outcome {
+        Ok(Ok(result)) => match CString::new(result.output.clone()) {
+            Ok(output) => {
+                *out_result = Box::into_raw(Box::new(ThdlResult { inner: result, output }));
+                ThdlStatus::Ok
+            }
+            Err(_) => fail(ThdlStatus::GenerationError, "generated output contains a NUL byte"),
+        },
+        Ok(Err(error)) => fail(status_for(&error), &format!("{:?}", error)),
+        Err(_) => fail(ThdlStatus::Panic, "synthesizer panicked"),
+    }
+}
+
+/// Generated netlist/HDL text, owned by the result handle
+///
+/// # Safety
+/// `result` must be NULL or a live handle from [`thdl_synthesize`].
+#[no_mangle]
+pub unsafe extern "C" fn thdl_result_output(result: *const ThdlResult) -> *const c_char {
+    match result.as_ref() {
+        Some(result) => result.output.as_ptr(),
+        None => ptr::null(),
+    }
+}
+
+/// Copy synthesis statistics into `out_stats`
+///
+/// # Safety
+/// `result` must be NULL or a live handle from [`thdl_synthesize`], and
+/// `out_stats` NULL or a valid pointer to writable storage.
+#[no_mangle]
+pub unsafe extern "C" fn thdl_result_stats(result: *const ThdlResult, out_stats: *mut ThdlStats) -> ThdlStatus {
+    let (Some(result), false) = (result.as_ref(), out_stats.is_null()) else {
+        return fail(ThdlStatus::NullPointer, "result or out_stats is NULL");
+    };
+
+    let stats = &result.inner.statistics;
+    *out_stats = ThdlStats {
+        trit_cells: stats.trit_cells as u64,
+        gates: stats.gates as u64,
+        flip_flops: stats.flip_flops as u64,
+        estimated_area_um2: stats.estimated_area_um2,
+        estimated_power_mw: stats.estimated_power_mw,
+        critical_path_ps: stats.critical_path_ps,
+    };
+    ThdlStatus::Ok
+}
+
+/// Free a result handle (NULL is ignored)
+///
+/// # Safety
+/// `result` must be NULL or a handle from [`thdl_synthesize`] not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn thdl_result_free(result: *mut ThdlResult) {
+    if !result.is_null() {
+        drop(Box::from_raw(result));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_synthesize_round_trip() {
+        unsafe {
+            let options = thdl_options_new();
+            assert_eq!(thdl_options_set_target(options, ThdlTarget::Asic as u32), ThdlStatus::Ok);
+
+            let source = CString::new("module alu ();\nendmodule\n").unwrap();
+            let mut result = ptr::null_mut();
+            assert_eq!(thdl_synthesize(source.as_ptr(), options, &mut result), ThdlStatus::Ok);
+
+            let output = CStr::from_ptr(thdl_result_output(result)).to_str().unwrap();
+            assert!(output.contains("Generated by THDL Synthesizer for ASIC"));
+
+            let mut stats = ThdlStats::default();
+            assert_eq!(thdl_result_stats(result, &mut stats), ThdlStatus::Ok);
+
+            thdl_result_free(result);
+            thdl_options_free(options);
+        }
+    }
+
+    #[test]
+    fn test_errors_are_reported() {
+        unsafe {
+            let mut result = ptr::null_mut();
+            assert_eq!(thdl_synthesize(ptr::null(), ptr::null(), &mut result), ThdlStatus::NullPointer);
+            assert!(result.is_null());
+
+            let message = CStr::from_ptr(thdl_last_error_message()).to_str().unwrap();
+            assert_eq!(message, "source is NULL");
+
+            let options = thdl_options_new();
+            assert_eq!(thdl_options_set_timing(options, 100, 60, 60), ThdlStatus::Ok);
+            let source = CString::new("module alu ();\nendmodule\n").unwrap();
+            assert_eq!(thdl_synthesize(source.as_ptr(), options, &mut result), ThdlStatus::TimingViolation);
+            assert!(result.is_null());
+
+            assert_eq!(thdl_options_set_target(options, 5), ThdlStatus::InvalidArgument);
+            assert_eq!(thdl_options_set_target(options, u32::MAX), ThdlStatus::InvalidArgument);
+            assert_eq!((*options).inner.target, Target::Simulation);
+            thdl_options_free(options);
+        }
+    }
+}
@@ -0,0 +1,230 @@
+//! GF(3) Semantic Conformance
+//!
+//! Canonical balanced-ternary GF(3) semantics plus an exhaustive
+//! cross-check harness. Every component that evaluates trit arithmetic
+//! registers a [`Gf3Implementation`] with a [`ConformanceSuite`]; any
+//! disagreement with the reference model is reported as a [`Mismatch`].
+//!
+//! Copyright (c) 2026 Capomastro Holdings Ltd. All rights reserved.
+
+use crate::ir::{BinaryOp, Expression, UnaryOp};
+use crate::optimizer;
+
+/// Balanced trit values in canonical order
+pub const TRITS: [i8; 3] = [-1, 0, 1];
+
+/// GF(3) operations covered by the suite
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gf3Op {
+    Add,
+    Mul,
+    Neg,
+    Rotate,
+    /// Ternary XOR as defined by THDL/TSL (minimum)
+    Xor,
+}
+
+impl Gf3Op {
+    pub const ALL: [Gf3Op; 5] = [Gf3Op::Add, Gf3Op::Mul, Gf3Op::Neg, Gf3Op::Rotate, Gf3Op::Xor];
+
+    pub fn arity(self) -> usize {
+        match self {
+            Gf3Op::Neg | Gf3Op::Rotate => 1,
+            Gf3Op::Add | Gf3Op::Mul | Gf3Op::Xor => 2,
+        }
+    }
+}
+
+/// Reference model: the canonical definition of each operation
+pub mod reference {
+    /// Map any integer to its balanced GF(3) representative
+    pub fn normalize(v: i32) -> i8 {
+        ((v + 1).rem_euclid(3) - 1) as i8
+    }
+
+    pub fn add(a: i8, b: i8) -> i8 {
+        normalize(a as i32 + b as i32)
+    }
+
+    pub fn mul(a: i8, b: i8) -> i8 {
+        normalize(a as i32 * b as i32)
+    }
+
+    pub fn neg(a: i8) -> i8 {
+        normalize(-(a as i32))
+    }
+
+    /// Bijective rotation: -1 -> 0 -> +1 -> -1
+    pub fn rotate(a: i8) -> i8 {
+        normalize(a as i32 + 1)
+    }
+
+    pub fn xor(a: i8, b: i8) -> i8 {
+        a.min(b)
+    }
+}
+
+/// Evaluate `op` with the reference model
+pub fn evaluate_reference(op: Gf3Op, a: i8, b: i8) -> i8 {
+    match op {
+        Gf3Op::Add => reference::add(a, b),
+        Gf3Op::Mul => reference::mul(a, b),
+        Gf3Op::Neg => reference::neg(a),
+        Gf3Op::Rotate => reference::rotate(a),
+        Gf3Op::Xor => reference::xor(a, b),
+    }
+}
+
+/// An implementation of GF(3) semantics under test
+pub trait Gf3Implementation {
+    /// Name used in mismatch reports
+    fn name(&self) -> &str;
+
+    /// Evaluate `op`; unary operations ignore `b`.
+    /// Returns `None` if the implementation does not provide `op`.
+    fn evaluate(&self, op: Gf3Op, a: i8, b: i8) -> Option<i8>;
+}
+
+/// A disagreement with the reference model
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub implementation: String,
+    pub op: Gf3Op,
+    pub operands: Vec<i8>,
+    pub expected: i8,
+    pub actual: i8,
+}
+
+/// Result of running the suite
+#[derive(Debug, Clone, Default)]
+pub struct ConformanceReport {
+    pub implementations: usize,
+    pub checks: usize,
+    pub mismatches: Vec<Mismatch>,
+}
+
+impl ConformanceReport {
+    pub fn passed(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+/// Registry of implementations checked against the reference model
+#[derive(Default)]
+pub struct ConformanceSuite {
+    implementations: Vec<Box<dyn Gf3Implementation>>,
+}
+
+impl ConformanceSuite {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&mut self, implementation: Box<dyn Gf3Implementation>) {
+        self.implementations.push(implementation);
+    }
+
+    /// Exhaustively check every registered implementation
+    pub fn run(&self) -> ConformanceReport {
+        let mut report = ConformanceReport {
+            implementations: self.implementations.len(),
+            ..Default::default()
+        };
+
+        for implementation in &self.implementations {
+            for op in Gf3Op::ALL {
+                let rhs: &[i8] = if op.arity() == 2 { &TRITS } else { &[0] };
+                for &a in &TRITS {
+                    for &b in rhs {
+                        let Some(actual) = implementation.evaluate(op, a, b) else {
+                            continue;
+                        };
+                        report.checks += 1;
+
+                        let expected = evaluate_reference(op, a, b);
+                        if actual != expected {
+                            report.mismatches.push(Mismatch {
+                                implementation: implementation.name().to_string(),
+                                op,
+                                operands: if op.arity() == 2 { vec![a, b] } else { vec![a] },
+                                expected,
+                                actual,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        report
+    }
+}
+
+/// THDL's compile-time evaluation (the constant folding pass)
+pub struct ThdlConstantFolding;
+
+impl Gf3Implementation for ThdlConstantFolding {
+    fn name(&self) -> &str {
+        "thdl::optimizer::constant_folding"
+    }
+
+    fn evaluate(&self, op: Gf3Op, a: i8, b: i8) -> Option<i8> {
+        let lhs = Box::new(Expression::TritLiteral(a));
+        let rhs = Box::new(Expression::TritLiteral(b));
+        let expr = match op {
+            Gf3Op::Add => Expression::BinaryOp(BinaryOp::TritAdd, lhs, rhs),
+            Gf3Op::Mul => Expression::BinaryOp(BinaryOp::TritMul, lhs, rhs),
+            Gf3Op::Xor => Expression::BinaryOp(BinaryOp::TritXor, lhs, rhs),
+            Gf3Op::Neg => Expression::UnaryOp(UnaryOp::TritNot, lhs),
+            Gf3Op::Rotate => Expression::UnaryOp(UnaryOp::TritRotate, lhs),
+        };
+
+        match optimizer::fold_constants(&expr) {
+            Expression::TritLiteral(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+/// Suite with every implementation available in this crate registered
+pub fn default_suite() -> ConformanceSuite {
+    let mut suite = ConformanceSuite::new();
+    suite.register(Box::new(ThdlConstantFolding));
+    suite
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Broken;
+
+    impl Gf3Implementation for Broken {
+        fn name(&self) -> &str {
+            "broken"
+        }
+
+        fn evaluate(&self, op: Gf3Op, a: i8, b: i8) -> Option<i8> {
+            match op {
+                Gf3Op::Add => Some(a.saturating_add(b).clamp(-1, 1)),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn test_registered_implementations_conform() {
+        let report = default_suite().run();
+        assert!(report.passed(), "{:?}", report.mismatches);
+        assert_eq!(report.checks, 3 * 9 + 2 * 3);
+    }
+
+    #[test]
+    fn test_drift_is_reported() {
+        let mut suite = ConformanceSuite::new();
+        suite.register(Box::new(Broken));
+        let report = suite.run();
+        assert_eq!(report.checks, 9);
+        assert_eq!(report.mismatches.len(), 2); // 1 + 1 and -1 + -1
+    }
+}
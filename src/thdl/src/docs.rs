@@ -0,0 +1,317 @@
+//! Documentation Extraction
+//!
+//! Collects `//!` file docs and `///` doc comments attached to item
+//! declarations, and emits them as structured JSON so API reference tooling
+//! does not need its own parser. The line scanner and JSON shape are shared
+//! with the TSL extractor; each language supplies its item keyword and a
+//! callback that reads the item header and finds the end of its body.
+
+use crate::{ir, SynthesisError};
+
+/// Documentation extracted from a source file
+#[derive(Debug, Clone, Default)]
+pub struct SourceDocs {
+    pub language: &'static str,
+    pub version: &'static str,
+    pub module_doc: String,
+    pub symbols: Vec<DocSymbol>,
+}
+
+/// A documented symbol
+#[derive(Debug, Clone)]
+pub struct DocSymbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    pub signature: String,
+    pub docs: String,
+    pub span: SourceSpan,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolKind {
+    Module,
+    Function,
+}
+
+impl SymbolKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SymbolKind::Module => "module",
+            SymbolKind::Function => "function",
+        }
+    }
+}
+
+/// Source location (1-based lines and columns, byte offsets)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceSpan {
+    pub start_line: usize,
+    pub start_column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+    pub start_offset: usize,
+    pub end_offset: usize,
+}
+
+impl SourceSpan {
+    /// Span covering `source[start..end]`
+    pub fn from_offsets(source: &str, start: usize, end: usize) -> Self {
+        let (start_line, start_column) = line_col(source, start);
+        let (end_line, end_column) = line_col(source, end);
+        Self {
+            start_line,
+            start_column,
+            end_line,
+            end_column,
+            start_offset: start,
+            end_offset: end,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"start_line\":{},\"start_column\":{},\"end_line\":{},\"end_column\":{},\"start_offset\":{},\"end_offset\":{}}}",
+            self.start_line,
+            self.start_column,
+            self.end_line,
+            self.end_column,
+            self.start_offset,
+            self.end_offset
+        )
+    }
+}
+
+/// Language settings for [`scan`]
+#[derive(Debug, Clone, Copy)]
+pub struct DocSyntax {
+    pub language: &'static str,
+    pub version: &'static str,
+    /// Keyword that starts a documented item, e.g. `module` or `fn`
+    pub keyword: &'static str,
+    pub kind: SymbolKind,
+}
+
+/// An item header read by the language callback passed to [`scan`]
+#[derive(Debug, Clone)]
+pub struct DocItem {
+    pub name: String,
+    pub signature: String,
+    /// Byte offset just past the end of the item's body
+    pub end: usize,
+}
+
+const THDL_SYNTAX: DocSyntax = DocSyntax {
+    language: "thdl",
+    version: crate::THDL_VERSION,
+    keyword: "module",
+    kind: SymbolKind::Module,
+};
+
+/// Extract documentation from THDL source
+pub fn extract(source: &str) -> Result<SourceDocs, SynthesisError> {
+    scan(source, &THDL_SYNTAX, module_item)
+}
+
+/// Collect the docs in `source`, calling `item` with the byte offset of each
+/// line that starts with `syntax.keyword`
+pub fn scan<E>(
+    source: &str,
+    syntax: &DocSyntax,
+    mut item: impl FnMut(&str, usize) -> Result<DocItem, E>,
+) -> Result<SourceDocs, E> {
+    let mut docs = SourceDocs {
+        language: syntax.language,
+        version: syntax.version,
+        ..Default::default()
+    };
+    let mut module_lines: Vec<&str> = Vec::new();
+    let mut pending: Vec<&str> = Vec::new();
+    let mut offset = 0;
+    let mut resume_at = 0;
+
+    for line in source.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+
+        // Skip the bodies of items already extracted
+        if line_start < resume_at {
+            continue;
+        }
+
+        let trimmed = line.trim_start();
+        let indent = line.len() - trimmed.len();
+        let is_item = trimmed
+            .strip_prefix(syntax.keyword)
+            .is_some_and(|rest| rest.starts_with(char::is_whitespace));
+
+        if let Some(text) = trimmed.strip_prefix("//!") {
+            module_lines.push(doc_text(text));
+        } else if trimmed.starts_with("///") && !trimmed.starts_with("////") {
+            pending.push(doc_text(&trimmed[3..]));
+        } else if is_item {
+            let start = line_start + indent;
+            let header = item(source, start)?;
+            resume_at = header.end;
+            docs.symbols.push(DocSymbol {
+                name: header.name,
+                kind: syntax.kind,
+                signature: header.signature,
+                docs: pending.join("\n"),
+                span: SourceSpan::from_offsets(source, start, header.end),
+            });
+            pending.clear();
+        } else if !trimmed.trim().is_empty() {
+            // Doc comments only attach to the item that follows them
+            pending.clear();
+        }
+    }
+
+    docs.module_doc = module_lines.join("\n");
+    Ok(docs)
+}
+
+fn module_item(source: &str, start: usize) -> Result<DocItem, SynthesisError> {
+    let header_end = find_outside_comments(source, start, ";")
+        .ok_or_else(|| SynthesisError::ParseError("Expected ';' after module header".into()))?;
+    let end = find_outside_comments(source, header_end, "endmodule")
+        .map(|i| i + "endmodule".len())
+        .ok_or_else(|| SynthesisError::ParseError("Expected endmodule".into()))?;
+
+    let module = ir::parse(&source[start..header_end])?;
+    let signature = source[start..header_end]
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace("( ", "(")
+        .replace(" )", ")");
+
+    Ok(DocItem {
+        name: module.name,
+        signature,
+        end,
+    })
+}
+
+/// Find `needle` at or after `from`, skipping `//` line comments.
+/// Word needles only match on identifier boundaries.
+fn find_outside_comments(source: &str, from: usize, needle: &str) -> Option<usize> {
+    let bytes = source.as_bytes();
+    let is_ident = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+    let word = needle.bytes().all(is_ident);
+    let mut i = from;
+
+    while i < bytes.len() {
+        if bytes[i] == b'/' && bytes.get(i + 1) == Some(&b'/') {
+            while i < bytes.len() && bytes[i] != b'\n' {
+                i += 1;
+            }
+            continue;
+        }
+        if bytes[i..].starts_with(needle.as_bytes()) {
+            let before_ok = i == 0 || !is_ident(bytes[i - 1]);
+            let after_ok = !bytes.get(i + needle.len()).is_some_and(|&b| is_ident(b));
+            if !word || (before_ok && after_ok) {
+                return Some(i);
+            }
+        }
+        i += 1;
+    }
+
+    None
+}
+
+/// Text of a doc comment line with the marker already stripped
+pub fn doc_text(text: &str) -> &str {
+    let text = text.strip_prefix(' ').unwrap_or(text);
+    text.trim_end()
+}
+
+/// 1-based line and column of a byte offset
+pub fn line_col(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = before.len() - before.rfind('\n').map(|i| i + 1).unwrap_or(0) + 1;
+    (line, column)
+}
+
+impl SourceDocs {
+    /// Serialize as a single JSON object
+    pub fn to_json(&self) -> String {
+        let symbols: Vec<String> = self.symbols.iter().map(DocSymbol::to_json).collect();
+        format!(
+            "{{\"language\":{},\"version\":{},\"module_doc\":{},\"symbols\":[{}]}}",
+            json_string(self.language),
+            json_string(self.version),
+            json_string(&self.module_doc),
+            symbols.join(",")
+        )
+    }
+}
+
+impl DocSymbol {
+    fn to_json(&self) -> String {
+        format!(
+            "{{\"name\":{},\"kind\":\"{}\",\"signature\":{},\"docs\":{},\"span\":{}}}",
+            json_string(&self.name),
+            self.kind.as_str(),
+            json_string(&self.signature),
+            json_string(&self.docs),
+            self.span.to_json()
+        )
+    }
+}
+
+/// Quote and escape `s` as a JSON string literal
+pub fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "//! Ternary ALU blocks\n\n/// Half adder\n/// over GF(3)\nmodule half_add (a, b);\n  // see endmodule docs\nendmodule\n\nmodule plain ();\nendmodule\n";
+
+    #[test]
+    fn test_extracts_documented_modules() {
+        let docs = extract(SOURCE).unwrap();
+        assert_eq!(docs.module_doc, "Ternary ALU blocks");
+        assert_eq!(docs.symbols.len(), 2);
+
+        let half_add = &docs.symbols[0];
+        assert_eq!(half_add.name, "half_add");
+        assert_eq!(half_add.signature, "module half_add (a, b)");
+        assert_eq!(half_add.docs, "Half adder\nover GF(3)");
+        assert_eq!((half_add.span.start_line, half_add.span.start_column), (5, 1));
+        assert_eq!((half_add.span.end_line, half_add.span.end_column), (7, 10));
+
+        let plain = &docs.symbols[1];
+        assert_eq!(plain.name, "plain");
+        assert!(plain.docs.is_empty());
+        assert_eq!((plain.span.start_line, plain.span.end_line), (9, 10));
+    }
+
+    #[test]
+    fn test_json_output() {
+        let json = extract(SOURCE).unwrap().to_json();
+        assert!(json.starts_with("{\"language\":\"thdl\""));
+        assert!(json.contains("\"module_doc\":\"Ternary ALU blocks\""));
+        assert!(json.contains("\"docs\":\"Half adder\\nover GF(3)\""));
+        assert!(json.contains("\"span\":{\"start_line\":5,\"start_column\":1,\"end_line\":7"));
+        assert_eq!(json_string("a\u{1b}\"b"), "\"a\\u001b\\\"b\"");
+    }
+}
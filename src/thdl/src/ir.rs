@@ -18,6 +18,19 @@ pub struct Module {
     pub instances: Vec<Instance>,
     pub assignments: Vec<Assignment>,
     pub always_blocks: Vec<AlwaysBlock>,
+    pub state_machines: Vec<StateMachine>,
+}
+
+impl Module {
+    /// True if the module has no ports and no logic
+    pub fn is_empty(&self) -> bool {
+        self.ports.is_empty()
+            && self.signals.is_empty()
+            && self.instances.is_empty()
+            && self.assignments.is_empty()
+            && self.always_blocks.is_empty()
+            && self.state_machines.is_empty()
+    }
 }
 
 /// Port definition
@@ -113,6 +126,8 @@ pub enum BinaryOp {
     Ne,
     Lt,
     Gt,
+    Le,
+    Ge,
 }
 
 /// Statement types
@@ -124,6 +139,186 @@ pub enum Statement {
     Block(Vec<Statement>),
 }
 
+/// State encoding for finite state machines
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StateEncoding {
+    /// One flip-flop per state
+    OneHot,
+    /// Binary-reflected Gray code
+    Gray,
+    /// Base-3 code, one 2-bit trit cell per digit
+    Ternary,
+}
+
+/// Finite state machine
+///
+/// Lowered during synthesis into a state register with synchronous reset
+/// and a `default` branch that returns any illegal encoding to the reset state.
+#[derive(Debug, Clone)]
+pub struct StateMachine {
+    pub name: String,
+    pub clock: String,
+    pub reset: String,
+    pub states: Vec<String>,
+    pub reset_state: String,
+    pub transitions: Vec<Transition>,
+    pub encoding: StateEncoding,
+}
+
+/// State transition, taken on the clock edge when `condition` holds
+#[derive(Debug, Clone)]
+pub struct Transition {
+    pub from: String,
+    pub to: String,
+    /// `None` for an unconditional transition
+    pub condition: Option<Expression>,
+}
+
+/// Largest state count a one-hot code can represent in a literal
+const MAX_ONE_HOT_STATES: usize = 63;
+
+impl StateMachine {
+    /// Name of the state register
+    pub fn state_register(&self) -> String {
+        format!("{}_state", self.name)
+    }
+
+    /// Width of the state register in bits
+    pub fn state_width(&self) -> usize {
+        let n = self.states.len().max(1);
+        match self.encoding {
+            StateEncoding::OneHot => n,
+            StateEncoding::Gray => ((usize::BITS - (n - 1).leading_zeros()) as usize).max(1),
+            StateEncoding::Ternary => self.ternary_digits() * 2,
+        }
+    }
+
+    fn ternary_digits(&self) -> usize {
+        let mut digits = 1;
+        let mut capacity = 3;
+        while capacity < self.states.len() {
+            capacity *= 3;
+            digits += 1;
+        }
+        digits
+    }
+
+    /// Register value encoding the state at `index`
+    pub fn state_code(&self, index: usize) -> i64 {
+        match self.encoding {
+            StateEncoding::OneHot => 1i64 << index,
+            StateEncoding::Gray => (index ^ (index >> 1)) as i64,
+            StateEncoding::Ternary => {
+                // Each digit uses the trit cell encoding 00/01/10; 11 is illegal
+                let mut code = 0i64;
+                let mut rest = index;
+                for digit in 0..self.ternary_digits() {
+                    code |= ((rest % 3) as i64) << (digit * 2);
+                    rest /= 3;
+                }
+                code
+            }
+        }
+    }
+
+    pub fn state_index(&self, state: &str) -> Option<usize> {
+        self.states.iter().position(|s| s == state)
+    }
+
+    /// Check that every referenced state is declared
+    pub fn validate(&self) -> Result<(), SynthesisError> {
+        let error = |msg: String| SynthesisError::GenerationError(format!("state machine '{}': {}", self.name, msg));
+
+        if self.states.is_empty() {
+            return Err(error("no states declared".into()));
+        }
+        if self.encoding == StateEncoding::OneHot && self.states.len() > MAX_ONE_HOT_STATES {
+            return Err(error(format!("one-hot encoding supports at most {} states", MAX_ONE_HOT_STATES)));
+        }
+        for (i, state) in self.states.iter().enumerate() {
+            if self.states[..i].contains(state) {
+                return Err(error(format!("duplicate state '{}'", state)));
+            }
+        }
+        if self.state_index(&self.reset_state).is_none() {
+            return Err(error(format!("unknown reset state '{}'", self.reset_state)));
+        }
+        for t in &self.transitions {
+            for state in [&t.from, &t.to] {
+                if self.state_index(state).is_none() {
+                    return Err(error(format!("unknown state '{}' in transition", state)));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// States that no transition path from the reset state can reach
+    pub fn unreachable_states(&self) -> Vec<String> {
+        let mut reached = vec![self.reset_state.as_str()];
+        let mut frontier = vec![self.reset_state.as_str()];
+
+        while let Some(state) = frontier.pop() {
+            for t in self.transitions.iter().filter(|t| t.from == state) {
+                if !reached.contains(&t.to.as_str()) {
+                    reached.push(&t.to);
+                    frontier.push(&t.to);
+                }
+            }
+        }
+
+        self.states.iter()
+            .filter(|s| !reached.contains(&s.as_str()))
+            .cloned()
+            .collect()
+    }
+
+    /// Lower into a state register and its clocked always block
+    pub fn lower(&self) -> Result<(Signal, AlwaysBlock), SynthesisError> {
+        self.validate()?;
+
+        let register = self.state_register();
+        let code = |state: &str| Expression::Literal(self.state_code(self.state_index(state).unwrap_or(0)));
+
+        let mut arms = Vec::with_capacity(self.states.len());
+        for (i, state) in self.states.iter().enumerate() {
+            // Earlier transitions take priority; an unconditional one ends the chain
+            let mut chain: Option<Vec<Statement>> = None;
+            for t in self.transitions.iter().rev().filter(|t| &t.from == state) {
+                let assign = Statement::Assign(register.clone(), code(&t.to));
+                chain = Some(match &t.condition {
+                    None => vec![assign],
+                    Some(cond) => vec![Statement::If(cond.clone(), vec![assign], chain)],
+                });
+            }
+            arms.push((Expression::Literal(self.state_code(i)), chain.unwrap_or_default()));
+        }
+
+        // Illegal-state recovery: any unlisted encoding returns to reset
+        let recovery = vec![Statement::Assign(register.clone(), code(&self.reset_state))];
+
+        let body = Statement::If(
+            Expression::Ident(self.reset.clone()),
+            vec![Statement::Assign(register.clone(), code(&self.reset_state))],
+            Some(vec![Statement::Case(Expression::Ident(register.clone()), arms, Some(recovery))]),
+        );
+
+        let signal = Signal {
+            name: register,
+            width: self.state_width(),
+            is_reg: true,
+            trit_type: self.encoding == StateEncoding::Ternary,
+        };
+        let block = AlwaysBlock {
+            sensitivity: Sensitivity::PosEdge(self.clock.clone()),
+            statements: vec![body],
+        };
+
+        Ok((signal, block))
+    }
+}
+
 /// Simple THDL parser
 struct Parser<'a> {
     source: &'a str,
@@ -156,6 +351,7 @@ impl<'a> Parser<'a> {
             instances: Vec::new(),
             assignments: Vec::new(),
             always_blocks: Vec::new(),
+            state_machines: Vec::new(),
         })
     }
     
@@ -195,3 +391,33 @@ impl<'a> Parser<'a> {
         Ok(self.source[start..self.pos].to_string())
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// State machine `ctl` (clock `clk`, reset `rst`) whose reset state is
+    /// the first of `states`; transitions are `(from, to, condition signal)`
+    pub(crate) fn state_machine(
+        states: &[&str],
+        transitions: &[(&str, &str, Option<&str>)],
+        encoding: StateEncoding,
+    ) -> StateMachine {
+        StateMachine {
+            name: "ctl".to_string(),
+            clock: "clk".to_string(),
+            reset: "rst".to_string(),
+            states: states.iter().map(|s| s.to_string()).collect(),
+            reset_state: states[0].to_string(),
+            transitions: transitions
+                .iter()
+                .map(|(from, to, condition)| Transition {
+                    from: from.to_string(),
+                    to: to.to_string(),
+                    condition: condition.map(|c| Expression::Ident(c.to_string())),
+                })
+                .collect(),
+            encoding,
+        }
+    }
+}
@@ -12,8 +12,12 @@
 //! # Copyright
 //! Copyright (c) 2026 Capomastro Holdings Ltd. All rights reserved.
 
+pub mod conformance;
+pub mod docs;
 pub mod ir;
+pub mod lint;
 pub mod optimizer;
+pub mod serialize;
 pub mod synthesizer;
 pub mod timing;
 
@@ -22,6 +26,7 @@ pub const THDL_VERSION: &str = "0.1.0";
 
 /// Supported synthesis targets
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Target {
     /// Xilinx FPGA family
     XilinxFpga,
@@ -37,6 +42,7 @@ pub enum Target {
 
 /// Synthesis options
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SynthesisOptions {
     pub target: Target,
     pub optimize_area: bool,
@@ -47,6 +53,7 @@ pub struct SynthesisOptions {
 
 /// Timing constraints for synthesis
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TimingConstraints {
     /// Maximum clock period in picoseconds
     pub max_clock_period_ps: u64,
@@ -56,6 +63,21 @@ pub struct TimingConstraints {
     pub hold_margin_ps: u64,
 }
 
+impl TimingConstraints {
+    /// Check that the setup and hold margins leave room in the clock period
+    /// (a zero period means unconstrained)
+    pub fn validate(&self) -> Result<(), SynthesisError> {
+        let required_ps = self.setup_margin_ps.saturating_add(self.hold_margin_ps);
+        if self.max_clock_period_ps != 0 && required_ps >= self.max_clock_period_ps {
+            return Err(SynthesisError::TimingViolation {
+                required_ps,
+                actual_ps: self.max_clock_period_ps,
+            });
+        }
+        Ok(())
+    }
+}
+
 impl Default for SynthesisOptions {
     fn default() -> Self {
         Self {
@@ -70,6 +92,8 @@ impl Default for SynthesisOptions {
 
 /// Synthesize THDL to target
 pub fn synthesize(thdl_source: &str, options: &SynthesisOptions) -> Result<SynthesisResult, SynthesisError> {
+    options.timing_constraints.validate()?;
+    
     // Parse THDL
     let ir = ir::parse(thdl_source)?;
     
@@ -91,6 +115,7 @@ pub struct SynthesisResult {
 
 /// Synthesis statistics
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SynthesisStats {
     pub trit_cells: usize,
     pub gates: usize,
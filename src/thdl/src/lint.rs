@@ -0,0 +1,97 @@
+//! THDL Lint Pass
+//!
+//! Design checks that do not block synthesis on their own but usually
+//! indicate a mistake, such as state machine states that can never be
+//! entered.
+
+use crate::ir::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintLevel {
+    Warning,
+    Error,
+}
+
+/// A lint finding
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintMessage {
+    pub level: LintLevel,
+    pub message: String,
+}
+
+/// Run all lint checks on a module
+pub fn lint(module: &Module) -> Vec<LintMessage> {
+    let mut messages = Vec::new();
+
+    for sm in &module.state_machines {
+        lint_state_machine(sm, &mut messages);
+    }
+
+    messages
+}
+
+fn lint_state_machine(sm: &StateMachine, messages: &mut Vec<LintMessage>) {
+    if let Err(e) = sm.validate() {
+        messages.push(LintMessage {
+            level: LintLevel::Error,
+            message: format!("{:?}", e),
+        });
+        return;
+    }
+
+    for state in sm.unreachable_states() {
+        messages.push(LintMessage {
+            level: LintLevel::Warning,
+            message: format!(
+                "state machine '{}': state '{}' is unreachable from reset state '{}'",
+                sm.name, state, sm.reset_state
+            ),
+        });
+    }
+
+    // Transitions listed after an unconditional one from the same state never fire
+    for state in &sm.states {
+        let mut shadowed = false;
+        for t in sm.transitions.iter().filter(|t| &t.from == state) {
+            if shadowed {
+                messages.push(LintMessage {
+                    level: LintLevel::Warning,
+                    message: format!(
+                        "state machine '{}': transition {} -> {} is shadowed by an earlier unconditional transition",
+                        sm.name, t.from, t.to
+                    ),
+                });
+            }
+            shadowed |= t.condition.is_none();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::tests::state_machine;
+
+    #[test]
+    fn test_flags_unreachable_and_shadowed() {
+        let module = Module {
+            name: "m".to_string(),
+            ports: vec![],
+            signals: vec![],
+            instances: vec![],
+            assignments: vec![],
+            always_blocks: vec![],
+            state_machines: vec![state_machine(
+                &["idle", "run", "lost"],
+                &[("idle", "run", None), ("idle", "idle", Some("hold")), ("run", "idle", Some("done"))],
+                StateEncoding::OneHot,
+            )],
+        };
+
+        let messages = lint(&module);
+        assert_eq!(messages.len(), 2);
+        assert!(messages.iter().all(|m| m.level == LintLevel::Warning));
+        assert!(messages[0].message.contains("'lost' is unreachable"));
+        assert!(messages[1].message.contains("idle -> idle is shadowed"));
+    }
+}
@@ -55,10 +55,18 @@ pub fn constant_folding(module: &Module) -> Result<Module, SynthesisError> {
         }
     }
     
+    for sm in &mut result.state_machines {
+        for transition in &mut sm.transitions {
+            if let Some(cond) = &mut transition.condition {
+                *cond = fold_constants(cond);
+            }
+        }
+    }
+    
     Ok(result)
 }
 
-fn fold_constants(expr: &Expression) -> Expression {
+pub(crate) fn fold_constants(expr: &Expression) -> Expression {
     match expr {
         Expression::BinaryOp(op, left, right) => {
             let left_folded = fold_constants(left);
@@ -68,8 +76,9 @@ fn fold_constants(expr: &Expression) -> Expression {
             match (&left_folded, &right_folded) {
                 (Expression::TritLiteral(a), Expression::TritLiteral(b)) => {
                     let result = match op {
-                        BinaryOp::TritAdd => (*a + *b).rem_euclid(3) as i8 - 1,
-                        BinaryOp::TritMul => (*a * *b).rem_euclid(3) as i8 - 1,
+                        // Balanced representative of the GF(3) result
+                        BinaryOp::TritAdd => (*a + *b + 1).rem_euclid(3) - 1,
+                        BinaryOp::TritMul => (*a * *b + 1).rem_euclid(3) - 1,
                         BinaryOp::TritXor => std::cmp::min(*a, *b),
                         _ => return Expression::BinaryOp(*op, Box::new(left_folded), Box::new(right_folded)),
                     };
@@ -179,6 +188,17 @@ pub fn dead_code_elimination(module: &Module) -> Result<Module, SynthesisError>
         }
     }
     
+    // State machines read their clock, reset and transition conditions
+    for sm in &result.state_machines {
+        used_signals.insert(sm.clock.clone());
+        used_signals.insert(sm.reset.clone());
+        for transition in &sm.transitions {
+            if let Some(cond) = &transition.condition {
+                collect_used_signals(cond, &mut used_signals);
+            }
+        }
+    }
+    
     // Remove unused signals
     result.signals.retain(|s| used_signals.contains(&s.name));
     
@@ -449,6 +469,7 @@ pub fn power_optimization(module: &Module) -> Result<Module, SynthesisError> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ir::tests::state_machine;
 
     #[test]
     fn test_constant_folding_trit_add() {
@@ -474,6 +495,35 @@ mod tests {
         assert!(matches!(optimized, Expression::Ident(_)));
     }
 
+    #[test]
+    fn test_state_machine_survives_optimization() {
+        let module = Module {
+            name: "ctl".to_string(),
+            ports: vec![],
+            signals: vec![Signal { name: "go".to_string(), width: 2, is_reg: false, trit_type: true }],
+            instances: vec![],
+            assignments: vec![],
+            always_blocks: vec![],
+            state_machines: vec![state_machine(
+                &["idle", "run"],
+                &[("idle", "run", Some("go"))],
+                StateEncoding::Ternary,
+            )],
+        };
+        let optimized = optimize(&module, &SynthesisOptions::default()).unwrap();
+        assert_eq!(optimized.state_machines.len(), 1);
+        assert_eq!(optimized.signals.len(), 1);
+
+        // Recovery branch is emitted as the case default
+        let (_, block) = optimized.state_machines[0].lower().unwrap();
+        match &block.statements[0] {
+            Statement::If(_, _, Some(else_stmts)) => {
+                assert!(matches!(&else_stmts[0], Statement::Case(_, _, Some(_))));
+            }
+            other => panic!("unexpected lowering: {:?}", other),
+        }
+    }
+
     #[test]
     fn test_multiply_by_zero() {
         let expr = Expression::BinaryOp(
@@ -0,0 +1,777 @@
+//! THDL IR Binary Serialization
+//!
+//! Stable, versioned encoding of [`Module`] for caching synthesis artifacts
+//! and exchanging externally generated IR.
+//!
+//! Layout (all integers little-endian):
+//! - magic `THIR`, format version `u16`, section count `u16`
+//! - sections: tag `u16`, payload length `u32`, payload
+//!
+//! New data is added as new sections, and list records are individually
+//! length-prefixed so trailing fields added by newer writers are skipped.
+//! A reader ignores unknown sections unless the tag has
+//! [`SECTION_REQUIRED`] set. The version is only bumped for changes that
+//! older readers cannot skip.
+//!
+//! Copyright (c) 2026 Capomastro Holdings Ltd. All rights reserved.
+
+use crate::ir::*;
+use crate::SynthesisError;
+use std::collections::HashMap;
+
+/// File magic
+pub const MAGIC: &[u8; 4] = b"THIR";
+
+/// Current format version
+pub const FORMAT_VERSION: u16 = 1;
+
+/// Tag bit marking a section that readers must understand
+pub const SECTION_REQUIRED: u16 = 0x8000;
+
+const SECTION_NAME: u16 = 1 | SECTION_REQUIRED;
+const SECTION_PORTS: u16 = 2 | SECTION_REQUIRED;
+const SECTION_SIGNALS: u16 = 3 | SECTION_REQUIRED;
+const SECTION_INSTANCES: u16 = 4 | SECTION_REQUIRED;
+const SECTION_ASSIGNMENTS: u16 = 5 | SECTION_REQUIRED;
+const SECTION_ALWAYS_BLOCKS: u16 = 6 | SECTION_REQUIRED;
+/// Only written when non-empty, so designs without FSMs stay readable by
+/// readers that predate this section
+const SECTION_STATE_MACHINES: u16 = 7 | SECTION_REQUIRED;
+
+/// Nesting limit for expressions and statements when decoding
+const MAX_DEPTH: usize = 256;
+
+/// Encode a module into the binary IR format
+pub fn encode(module: &Module) -> Vec<u8> {
+    let mut sections: Vec<(u16, Writer)> = Vec::new();
+
+    let mut name = Writer::new();
+    name.string(&module.name);
+    sections.push((SECTION_NAME, name));
+
+    sections.push((SECTION_PORTS, encode_list(&module.ports, encode_port)));
+    sections.push((SECTION_SIGNALS, encode_list(&module.signals, encode_signal)));
+    sections.push((SECTION_INSTANCES, encode_list(&module.instances, encode_instance)));
+    sections.push((SECTION_ASSIGNMENTS, encode_list(&module.assignments, encode_assignment)));
+    sections.push((SECTION_ALWAYS_BLOCKS, encode_list(&module.always_blocks, encode_always_block)));
+    if !module.state_machines.is_empty() {
+        sections.push((SECTION_STATE_MACHINES, encode_list(&module.state_machines, encode_state_machine)));
+    }
+
+    let mut out = Writer::new();
+    out.bytes(MAGIC);
+    out.u16(FORMAT_VERSION);
+    out.u16(sections.len() as u16);
+    for (tag, payload) in sections {
+        out.u16(tag);
+        out.u32(payload.buf.len() as u32);
+        out.bytes(&payload.buf);
+    }
+    out.buf
+}
+
+/// Decode a module from the binary IR format
+pub fn decode(bytes: &[u8]) -> Result<Module, SynthesisError> {
+    let mut reader = Reader::new(bytes);
+
+    if reader.take(4)? != MAGIC {
+        return Err(decode_error("bad magic"));
+    }
+    let version = reader.u16()?;
+    if version == 0 || version > FORMAT_VERSION {
+        return Err(decode_error(&format!("unsupported format version {}", version)));
+    }
+
+    let mut module = Module {
+        name: String::new(),
+        ports: Vec::new(),
+        signals: Vec::new(),
+        instances: Vec::new(),
+        assignments: Vec::new(),
+        always_blocks: Vec::new(),
+        state_machines: Vec::new(),
+    };
+    let mut has_name = false;
+
+    let section_count = reader.u16()?;
+    for _ in 0..section_count {
+        let tag = reader.u16()?;
+        let len = reader.u32()? as usize;
+        let mut section = Reader::new(reader.take(len)?);
+
+        match tag {
+            SECTION_NAME => {
+                module.name = section.string()?;
+                has_name = true;
+            }
+            SECTION_PORTS => module.ports = decode_list(&mut section, decode_port)?,
+            SECTION_SIGNALS => module.signals = decode_list(&mut section, decode_signal)?,
+            SECTION_INSTANCES => module.instances = decode_list(&mut section, decode_instance)?,
+            SECTION_ASSIGNMENTS => module.assignments = decode_list(&mut section, decode_assignment)?,
+            SECTION_ALWAYS_BLOCKS => {
+                module.always_blocks = decode_list(&mut section, decode_always_block)?
+            }
+            SECTION_STATE_MACHINES => {
+                module.state_machines = decode_list(&mut section, decode_state_machine)?
+            }
+            _ if tag & SECTION_REQUIRED != 0 => {
+                return Err(decode_error(&format!("unknown required section 0x{:04x}", tag)));
+            }
+            _ => {} // Optional section from a newer writer
+        }
+    }
+
+    if !has_name {
+        return Err(decode_error("missing module name section"));
+    }
+
+    Ok(module)
+}
+
+fn decode_error(message: &str) -> SynthesisError {
+    SynthesisError::ParseError(format!("IR decode: {}", message))
+}
+
+// ---------------------------------------------------------------------------
+// Records
+// ---------------------------------------------------------------------------
+
+fn encode_list<T>(items: &[T], encode_item: fn(&mut Writer, &T)) -> Writer {
+    let mut w = Writer::new();
+    w.u32(items.len() as u32);
+    for item in items {
+        let mut record = Writer::new();
+        encode_item(&mut record, item);
+        w.u32(record.buf.len() as u32);
+        w.bytes(&record.buf);
+    }
+    w
+}
+
+fn decode_list<T>(
+    r: &mut Reader,
+    decode_item: fn(&mut Reader) -> Result<T, SynthesisError>,
+) -> Result<Vec<T>, SynthesisError> {
+    let count = r.u32()? as usize;
+    let mut items = Vec::with_capacity(count.min(1024));
+    for _ in 0..count {
+        let len = r.u32()? as usize;
+        // Fields appended by newer writers are left unread
+        let mut record = Reader::new(r.take(len)?);
+        items.push(decode_item(&mut record)?);
+    }
+    Ok(items)
+}
+
+fn encode_port(w: &mut Writer, port: &Port) {
+    w.string(&port.name);
+    w.u8(match port.direction {
+        PortDirection::Input => 0,
+        PortDirection::Output => 1,
+        PortDirection::InOut => 2,
+    });
+    w.u32(port.width as u32);
+    w.bool(port.trit_type);
+}
+
+fn decode_port(r: &mut Reader) -> Result<Port, SynthesisError> {
+    let name = r.string()?;
+    let direction = match r.u8()? {
+        0 => PortDirection::Input,
+        1 => PortDirection::Output,
+        2 => PortDirection::InOut,
+        d => return Err(decode_error(&format!("invalid port direction {}", d))),
+    };
+    Ok(Port {
+        name,
+        direction,
+        width: r.u32()? as usize,
+        trit_type: r.bool()?,
+    })
+}
+
+fn encode_signal(w: &mut Writer, signal: &Signal) {
+    w.string(&signal.name);
+    w.u32(signal.width as u32);
+    w.bool(signal.is_reg);
+    w.bool(signal.trit_type);
+}
+
+fn decode_signal(r: &mut Reader) -> Result<Signal, SynthesisError> {
+    Ok(Signal {
+        name: r.string()?,
+        width: r.u32()? as usize,
+        is_reg: r.bool()?,
+        trit_type: r.bool()?,
+    })
+}
+
+fn encode_instance(w: &mut Writer, instance: &Instance) {
+    w.string(&instance.module_name);
+    w.string(&instance.instance_name);
+
+    // Sorted so identical modules always encode to identical bytes
+    let mut connections: Vec<_> = instance.port_connections.iter().collect();
+    connections.sort();
+    w.u32(connections.len() as u32);
+    for (port, net) in connections {
+        w.string(port);
+        w.string(net);
+    }
+}
+
+fn decode_instance(r: &mut Reader) -> Result<Instance, SynthesisError> {
+    let module_name = r.string()?;
+    let instance_name = r.string()?;
+    let count = r.u32()? as usize;
+    let mut port_connections = HashMap::new();
+    for _ in 0..count {
+        let port = r.string()?;
+        let net = r.string()?;
+        port_connections.insert(port, net);
+    }
+    Ok(Instance { module_name, instance_name, port_connections })
+}
+
+fn encode_assignment(w: &mut Writer, assignment: &Assignment) {
+    w.string(&assignment.target);
+    encode_expression(w, &assignment.expression);
+}
+
+fn decode_assignment(r: &mut Reader) -> Result<Assignment, SynthesisError> {
+    Ok(Assignment {
+        target: r.string()?,
+        expression: decode_expression(r, 0)?,
+    })
+}
+
+fn encode_always_block(w: &mut Writer, block: &AlwaysBlock) {
+    match &block.sensitivity {
+        Sensitivity::Combinational => w.u8(0),
+        Sensitivity::PosEdge(sig) => { w.u8(1); w.string(sig); }
+        Sensitivity::NegEdge(sig) => { w.u8(2); w.string(sig); }
+        Sensitivity::Both(sig) => { w.u8(3); w.string(sig); }
+    }
+    encode_statements(w, &block.statements);
+}
+
+fn decode_always_block(r: &mut Reader) -> Result<AlwaysBlock, SynthesisError> {
+    let sensitivity = match r.u8()? {
+        0 => Sensitivity::Combinational,
+        1 => Sensitivity::PosEdge(r.string()?),
+        2 => Sensitivity::NegEdge(r.string()?),
+        3 => Sensitivity::Both(r.string()?),
+        s => return Err(decode_error(&format!("invalid sensitivity {}", s))),
+    };
+    Ok(AlwaysBlock {
+        sensitivity,
+        statements: decode_statements(r, 0)?,
+    })
+}
+
+fn encode_state_machine(w: &mut Writer, sm: &StateMachine) {
+    w.string(&sm.name);
+    w.string(&sm.clock);
+    w.string(&sm.reset);
+    w.u8(match sm.encoding {
+        StateEncoding::OneHot => 0,
+        StateEncoding::Gray => 1,
+        StateEncoding::Ternary => 2,
+    });
+    w.u32(sm.states.len() as u32);
+    for state in &sm.states {
+        w.string(state);
+    }
+    w.string(&sm.reset_state);
+    w.u32(sm.transitions.len() as u32);
+    for t in &sm.transitions {
+        w.string(&t.from);
+        w.string(&t.to);
+        match &t.condition {
+            Some(cond) => { w.bool(true); encode_expression(w, cond); }
+            None => w.bool(false),
+        }
+    }
+}
+
+fn decode_state_machine(r: &mut Reader) -> Result<StateMachine, SynthesisError> {
+    let name = r.string()?;
+    let clock = r.string()?;
+    let reset = r.string()?;
+    let encoding = match r.u8()? {
+        0 => StateEncoding::OneHot,
+        1 => StateEncoding::Gray,
+        2 => StateEncoding::Ternary,
+        e => return Err(decode_error(&format!("invalid state encoding {}", e))),
+    };
+    let count = r.u32()? as usize;
+    let mut states = Vec::with_capacity(count.min(1024));
+    for _ in 0..count {
+        states.push(r.string()?);
+    }
+    let reset_state = r.string()?;
+    let count = r.u32()? as usize;
+    let mut transitions = Vec::with_capacity(count.min(1024));
+    for _ in 0..count {
+        let from = r.string()?;
+        let to = r.string()?;
+        let condition = if r.bool()? { Some(decode_expression(r, 0)?) } else { None };
+        transitions.push(Transition { from, to, condition });
+    }
+    Ok(StateMachine { name, clock, reset, states, reset_state, transitions, encoding })
+}
+
+// ---------------------------------------------------------------------------
+// Expressions and statements
+// ---------------------------------------------------------------------------
+
+fn encode_expression(w: &mut Writer, expr: &Expression) {
+    match expr {
+        Expression::Ident(name) => { w.u8(0); w.string(name); }
+        Expression::Literal(v) => { w.u8(1); w.i64(*v); }
+        Expression::TritLiteral(v) => { w.u8(2); w.u8(*v as u8); }
+        Expression::BitSelect(inner, bit) => {
+            w.u8(3);
+            encode_expression(w, inner);
+            w.u32(*bit as u32);
+        }
+        Expression::RangeSelect(inner, high, low) => {
+            w.u8(4);
+            encode_expression(w, inner);
+            w.u32(*high as u32);
+            w.u32(*low as u32);
+        }
+        Expression::Concat(exprs) => {
+            w.u8(5);
+            w.u32(exprs.len() as u32);
+            for e in exprs {
+                encode_expression(w, e);
+            }
+        }
+        Expression::UnaryOp(op, inner) => {
+            w.u8(6);
+            w.u8(unary_op_code(*op));
+            encode_expression(w, inner);
+        }
+        Expression::BinaryOp(op, left, right) => {
+            w.u8(7);
+            w.u8(binary_op_code(*op));
+            encode_expression(w, left);
+            encode_expression(w, right);
+        }
+        Expression::TernaryOp(cond, then_expr, else_expr) => {
+            w.u8(8);
+            encode_expression(w, cond);
+            encode_expression(w, then_expr);
+            encode_expression(w, else_expr);
+        }
+        Expression::FunctionCall(name, args) => {
+            w.u8(9);
+            w.string(name);
+            w.u32(args.len() as u32);
+            for arg in args {
+                encode_expression(w, arg);
+            }
+        }
+    }
+}
+
+fn decode_expression(r: &mut Reader, depth: usize) -> Result<Expression, SynthesisError> {
+    if depth > MAX_DEPTH {
+        return Err(decode_error("expression nesting too deep"));
+    }
+    let next = depth + 1;
+
+    Ok(match r.u8()? {
+        0 => Expression::Ident(r.string()?),
+        1 => Expression::Literal(r.i64()?),
+        2 => match r.u8()? as i8 {
+            t @ -1..=1 => Expression::TritLiteral(t),
+            _ => return Err(decode_error("invalid trit literal")),
+        },
+        3 => {
+            let inner = decode_expression(r, next)?;
+            Expression::BitSelect(Box::new(inner), r.u32()? as usize)
+        }
+        4 => {
+            let inner = decode_expression(r, next)?;
+            let high = r.u32()? as usize;
+            let low = r.u32()? as usize;
+            Expression::RangeSelect(Box::new(inner), high, low)
+        }
+        5 => {
+            let count = r.u32()? as usize;
+            let mut exprs = Vec::with_capacity(count.min(1024));
+            for _ in 0..count {
+                exprs.push(decode_expression(r, next)?);
+            }
+            Expression::Concat(exprs)
+        }
+        6 => {
+            let op = unary_op_from_code(r.u8()?)?;
+            Expression::UnaryOp(op, Box::new(decode_expression(r, next)?))
+        }
+        7 => {
+            let op = binary_op_from_code(r.u8()?)?;
+            let left = decode_expression(r, next)?;
+            let right = decode_expression(r, next)?;
+            Expression::BinaryOp(op, Box::new(left), Box::new(right))
+        }
+        8 => {
+            let cond = decode_expression(r, next)?;
+            let then_expr = decode_expression(r, next)?;
+            let else_expr = decode_expression(r, next)?;
+            Expression::TernaryOp(Box::new(cond), Box::new(then_expr), Box::new(else_expr))
+        }
+        9 => {
+            let name = r.string()?;
+            let count = r.u32()? as usize;
+            let mut args = Vec::with_capacity(count.min(1024));
+            for _ in 0..count {
+                args.push(decode_expression(r, next)?);
+            }
+            Expression::FunctionCall(name, args)
+        }
+        t => return Err(decode_error(&format!("invalid expression tag {}", t))),
+    })
+}
+
+fn encode_statements(w: &mut Writer, stmts: &[Statement]) {
+    w.u32(stmts.len() as u32);
+    for stmt in stmts {
+        encode_statement(w, stmt);
+    }
+}
+
+fn decode_statements(r: &mut Reader, depth: usize) -> Result<Vec<Statement>, SynthesisError> {
+    let count = r.u32()? as usize;
+    let mut stmts = Vec::with_capacity(count.min(1024));
+    for _ in 0..count {
+        stmts.push(decode_statement(r, depth)?);
+    }
+    Ok(stmts)
+}
+
+fn encode_statement(w: &mut Writer, stmt: &Statement) {
+    match stmt {
+        Statement::Assign(target, expr) => {
+            w.u8(0);
+            w.string(target);
+            encode_expression(w, expr);
+        }
+        Statement::If(cond, then_stmts, else_stmts) => {
+            w.u8(1);
+            encode_expression(w, cond);
+            encode_statements(w, then_stmts);
+            match else_stmts {
+                Some(stmts) => { w.bool(true); encode_statements(w, stmts); }
+                None => w.bool(false),
+            }
+        }
+        Statement::Case(expr, cases, default) => {
+            w.u8(2);
+            encode_expression(w, expr);
+            w.u32(cases.len() as u32);
+            for (case_expr, case_stmts) in cases {
+                encode_expression(w, case_expr);
+                encode_statements(w, case_stmts);
+            }
+            match default {
+                Some(stmts) => { w.bool(true); encode_statements(w, stmts); }
+                None => w.bool(false),
+            }
+        }
+        Statement::Block(stmts) => {
+            w.u8(3);
+            encode_statements(w, stmts);
+        }
+    }
+}
+
+fn decode_statement(r: &mut Reader, depth: usize) -> Result<Statement, SynthesisError> {
+    if depth > MAX_DEPTH {
+        return Err(decode_error("statement nesting too deep"));
+    }
+    let next = depth + 1;
+
+    Ok(match r.u8()? {
+        0 => {
+            let target = r.string()?;
+            Statement::Assign(target, decode_expression(r, next)?)
+        }
+        1 => {
+            let cond = decode_expression(r, next)?;
+            let then_stmts = decode_statements(r, next)?;
+            let else_stmts = if r.bool()? { Some(decode_statements(r, next)?) } else { None };
+            Statement::If(cond, then_stmts, else_stmts)
+        }
+        2 => {
+            let expr = decode_expression(r, next)?;
+            let count = r.u32()? as usize;
+            let mut cases = Vec::with_capacity(count.min(1024));
+            for _ in 0..count {
+                let case_expr = decode_expression(r, next)?;
+                cases.push((case_expr, decode_statements(r, next)?));
+            }
+            let default = if r.bool()? { Some(decode_statements(r, next)?) } else { None };
+            Statement::Case(expr, cases, default)
+        }
+        3 => Statement::Block(decode_statements(r, next)?),
+        t => return Err(decode_error(&format!("invalid statement tag {}", t))),
+    })
+}
+
+fn unary_op_code(op: UnaryOp) -> u8 {
+    match op {
+        UnaryOp::Not => 0,
+        UnaryOp::TritNot => 1,
+        UnaryOp::TritRotate => 2,
+        UnaryOp::Reduce => 3,
+    }
+}
+
+fn unary_op_from_code(code: u8) -> Result<UnaryOp, SynthesisError> {
+    Ok(match code {
+        0 => UnaryOp::Not,
+        1 => UnaryOp::TritNot,
+        2 => UnaryOp::TritRotate,
+        3 => UnaryOp::Reduce,
+        c => return Err(decode_error(&format!("invalid unary op {}", c))),
+    })
+}
+
+fn binary_op_code(op: BinaryOp) -> u8 {
+    match op {
+        BinaryOp::And => 0,
+        BinaryOp::Or => 1,
+        BinaryOp::Xor => 2,
+        BinaryOp::TritAdd => 3,
+        BinaryOp::TritMul => 4,
+        BinaryOp::TritXor => 5,
+        BinaryOp::Add => 6,
+        BinaryOp::Sub => 7,
+        BinaryOp::Mul => 8,
+        BinaryOp::Eq => 9,
+        BinaryOp::Ne => 10,
+        BinaryOp::Lt => 11,
+        BinaryOp::Gt => 12,
+        BinaryOp::Le => 13,
+        BinaryOp::Ge => 14,
+    }
+}
+
+fn binary_op_from_code(code: u8) -> Result<BinaryOp, SynthesisError> {
+    Ok(match code {
+        0 => BinaryOp::And,
+        1 => BinaryOp::Or,
+        2 => BinaryOp::Xor,
+        3 => BinaryOp::TritAdd,
+        4 => BinaryOp::TritMul,
+        5 => BinaryOp::TritXor,
+        6 => BinaryOp::Add,
+        7 => BinaryOp::Sub,
+        8 => BinaryOp::Mul,
+        9 => BinaryOp::Eq,
+        10 => BinaryOp::Ne,
+        11 => BinaryOp::Lt,
+        12 => BinaryOp::Gt,
+        13 => BinaryOp::Le,
+        14 => BinaryOp::Ge,
+        c => return Err(decode_error(&format!("invalid binary op {}", c))),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Byte-level helpers
+// ---------------------------------------------------------------------------
+
+struct Writer {
+    buf: Vec<u8>,
+}
+
+impl Writer {
+    fn new() -> Self {
+        Self { buf: Vec::new() }
+    }
+
+    fn bytes(&mut self, b: &[u8]) {
+        self.buf.extend_from_slice(b);
+    }
+
+    fn u8(&mut self, v: u8) {
+        self.buf.push(v);
+    }
+
+    fn bool(&mut self, v: bool) {
+        self.buf.push(v as u8);
+    }
+
+    fn u16(&mut self, v: u16) {
+        self.bytes(&v.to_le_bytes());
+    }
+
+    fn u32(&mut self, v: u32) {
+        self.bytes(&v.to_le_bytes());
+    }
+
+    fn i64(&mut self, v: i64) {
+        self.bytes(&v.to_le_bytes());
+    }
+
+    fn string(&mut self, s: &str) {
+        self.u32(s.len() as u32);
+        self.bytes(s.as_bytes());
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], SynthesisError> {
+        let end = self.pos.checked_add(len)
+            .filter(|&end| end <= self.data.len())
+            .ok_or_else(|| decode_error("unexpected end of data"))?;
+        let slice = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn u8(&mut self) -> Result<u8, SynthesisError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn bool(&mut self) -> Result<bool, SynthesisError> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            b => Err(decode_error(&format!("invalid bool {}", b))),
+        }
+    }
+
+    fn u16(&mut self) -> Result<u16, SynthesisError> {
+        let b = self.take(2)?;
+        Ok(u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, SynthesisError> {
+        let b = self.take(4)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn i64(&mut self) -> Result<i64, SynthesisError> {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(self.take(8)?);
+        Ok(i64::from_le_bytes(bytes))
+    }
+
+    fn string(&mut self) -> Result<String, SynthesisError> {
+        let len = self.u32()? as usize;
+        String::from_utf8(self.take(len)?.to_vec())
+            .map_err(|_| decode_error("invalid UTF-8 in string"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::tests::state_machine;
+
+    fn sample_module() -> Module {
+        let mut connections = HashMap::new();
+        connections.insert("a".to_string(), "x".to_string());
+        Module {
+            name: "alu".to_string(),
+            ports: vec![Port {
+                name: "x".to_string(),
+                direction: PortDirection::Input,
+                width: 2,
+                trit_type: true,
+            }],
+            signals: vec![],
+            instances: vec![Instance {
+                module_name: "trit_cell".to_string(),
+                instance_name: "u0".to_string(),
+                port_connections: connections,
+            }],
+            assignments: vec![Assignment {
+                target: "y".to_string(),
+                expression: Expression::BinaryOp(
+                    BinaryOp::TritAdd,
+                    Box::new(Expression::Ident("x".to_string())),
+                    Box::new(Expression::TritLiteral(-1)),
+                ),
+            }],
+            always_blocks: vec![AlwaysBlock {
+                sensitivity: Sensitivity::PosEdge("clk".to_string()),
+                statements: vec![Statement::If(
+                    Expression::Ident("rst".to_string()),
+                    vec![Statement::Assign("q".to_string(), Expression::TritLiteral(0))],
+                    None,
+                )],
+            }],
+            state_machines: vec![],
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let module = sample_module();
+        let bytes = encode(&module);
+        let decoded = decode(&bytes).unwrap();
+        assert_eq!(format!("{:?}", decoded), format!("{:?}", module));
+        assert_eq!(encode(&decoded), bytes);
+    }
+
+    #[test]
+    fn test_state_machine_round_trip() {
+        let mut module = sample_module();
+        module.state_machines.push(state_machine(
+            &["idle", "run"],
+            &[("idle", "run", Some("go"))],
+            StateEncoding::Gray,
+        ));
+        let decoded = decode(&encode(&module)).unwrap();
+        assert_eq!(format!("{:?}", decoded), format!("{:?}", module));
+    }
+
+    #[test]
+    fn test_skips_unknown_optional_section() {
+        let mut bytes = encode(&sample_module());
+        bytes[6] += 1; // section count
+        bytes.extend_from_slice(&0x0042u16.to_le_bytes());
+        bytes.extend_from_slice(&3u32.to_le_bytes());
+        bytes.extend_from_slice(&[1, 2, 3]);
+        assert_eq!(decode(&bytes).unwrap().name, "alu");
+
+        // The same section marked required must be rejected
+        let len = bytes.len();
+        bytes[len - 9..len - 7].copy_from_slice(&(0x0042u16 | SECTION_REQUIRED).to_le_bytes());
+        assert!(decode(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_rejects_truncated_input() {
+        let bytes = encode(&sample_module());
+        assert!(decode(&bytes[..bytes.len() - 1]).is_err());
+        assert!(decode(b"NOPE").is_err());
+    }
+
+    #[test]
+    fn test_rejects_invalid_trit_literal() {
+        let mut module = sample_module();
+        module.assignments[0].expression = Expression::BinaryOp(
+            BinaryOp::TritMul,
+            Box::new(Expression::TritLiteral(100)),
+            Box::new(Expression::TritLiteral(100)),
+        );
+        let err = decode(&encode(&module)).unwrap_err();
+        assert!(format!("{:?}", err).contains("invalid trit literal"));
+    }
+}
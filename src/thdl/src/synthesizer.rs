@@ -109,7 +109,14 @@ fn generate_simulation(module: &Module) -> Result<String, SynthesisError> {
     Ok(output)
 }
 
+/// Render a module as THDL/Verilog text, lowering any state machines
+pub fn module_to_verilog(module: &Module) -> Result<String, SynthesisError> {
+    generate_verilog_module(module)
+}
+
 fn generate_verilog_module(module: &Module) -> Result<String, SynthesisError> {
+    let lowered = lower_state_machines(module)?;
+    let module = &lowered;
     let mut output = String::new();
     
     // Module declaration
@@ -176,6 +183,17 @@ fn generate_verilog_module(module: &Module) -> Result<String, SynthesisError> {
     Ok(output)
 }
 
+/// Replace state machines with their state registers and always blocks
+fn lower_state_machines(module: &Module) -> Result<Module, SynthesisError> {
+    let mut result = module.clone();
+    for sm in result.state_machines.drain(..) {
+        let (signal, block) = sm.lower()?;
+        result.signals.push(signal);
+        result.always_blocks.push(block);
+    }
+    Ok(result)
+}
+
 fn expression_to_verilog(expr: &Expression) -> String {
     match expr {
         Expression::Ident(name) => name.clone(),
@@ -199,6 +217,8 @@ fn expression_to_verilog(expr: &Expression) -> String {
                 BinaryOp::Ne => "!=",
                 BinaryOp::Lt => "<",
                 BinaryOp::Gt => ">",
+                BinaryOp::Le => "<=",
+                BinaryOp::Ge => ">=",
                 BinaryOp::TritAdd => return format!("trit_add({}, {})", l, r),
                 BinaryOp::TritMul => return format!("trit_mul({}, {})", l, r),
                 BinaryOp::TritXor => return format!("trit_xor({}, {})", l, r),
@@ -301,6 +321,11 @@ fn calculate_statistics(module: &Module) -> SynthesisStats {
         }
     }
     
+    // State registers
+    for sm in &module.state_machines {
+        stats.flip_flops += sm.state_width();
+    }
+    
     // Estimate gates from expressions
     for assignment in &module.assignments {
         stats.gates += count_gates(&assignment.expression);
@@ -4,6 +4,7 @@
 #[derive(Debug, Clone)]
 pub struct Program {
     pub functions: Vec<Function>,
+    pub state_machines: Vec<StateMachine>,
 }
 
 /// Function definition
@@ -15,6 +16,43 @@ pub struct Function {
     pub body: Vec<Statement>,
 }
 
+/// Finite state machine declaration
+///
+/// ```text
+/// fsm ctl(clk, rst): gray {
+///     state idle, run;
+///     reset idle;
+///     idle -> run if start;
+///     run -> idle;
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct StateMachine {
+    pub name: String,
+    pub clock: String,
+    pub reset: String,
+    pub encoding: StateEncoding,
+    pub states: Vec<String>,
+    pub reset_state: String,
+    pub transitions: Vec<Transition>,
+}
+
+/// State register encoding
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StateEncoding {
+    OneHot,
+    Gray,
+    Ternary,
+}
+
+/// State transition (`from -> to [if condition];`)
+#[derive(Debug, Clone)]
+pub struct Transition {
+    pub from: String,
+    pub to: String,
+    pub condition: Option<Expression>,
+}
+
 /// Function parameter
 #[derive(Debug, Clone)]
 pub struct Parameter {
@@ -0,0 +1,256 @@
+//! ternaryc - TSL/THDL to netlist driver
+//!
+//! Runs the flow (TSL -> THDL -> optimized netlist) for a single source
+//! file and writes the netlist plus a statistics report. The THDL text
+//! parser currently reads only the first module header, so ports and logic
+//! do not reach the netlist yet; a warning is printed when that happens.
+//!
+//! # Copyright
+//! Copyright (c) 2026 Capomastro Holdings Ltd. All rights reserved.
+
+use plenumnet_thdl::docs::json_string;
+use plenumnet_thdl::lint::LintLevel;
+use plenumnet_thdl::{SynthesisOptions, SynthesisResult, SynthesisStats, Target, TimingConstraints};
+use plenumnet_tsl::project::{self, SourceKind};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::{env, fs};
+
+const USAGE: &str = "\
+usage: ternaryc <input.tsl|input.thdl> [options]
+
+options:
+  -o, --output <file>        netlist output (default: <input>.v)
+  --stats <file>             statistics report (default: <input>.stats.json)
+  --emit-thdl <file>         also write the intermediate THDL
+  --target <name>            xilinx | intel | lattice | asic | sim (default: sim)
+  --constraints <file>       timing constraint file; repeatable, later files
+                             override only the keys they set
+  -O0                        disable all optimization passes
+  --[no-]optimize-area       toggle area optimization (default: on)
+  --[no-]optimize-speed      toggle speed optimization (default: on)
+  --[no-]optimize-power      toggle power optimization (default: off)
+  -h, --help                 print this message";
+
+/// Parsed command line
+#[derive(Debug, Default)]
+struct Args {
+    input: PathBuf,
+    output: Option<PathBuf>,
+    stats: Option<PathBuf>,
+    emit_thdl: Option<PathBuf>,
+    constraint_files: Vec<PathBuf>,
+    options: SynthesisOptions,
+}
+
+fn parse_target(name: &str) -> Result<Target, String> {
+    match name {
+        "xilinx" => Ok(Target::XilinxFpga),
+        "intel" => Ok(Target::IntelFpga),
+        "lattice" => Ok(Target::LatticeFpga),
+        "asic" => Ok(Target::Asic),
+        "sim" | "simulation" => Ok(Target::Simulation),
+        other => Err(format!("unknown target '{}'", other)),
+    }
+}
+
+fn parse_args<I: Iterator<Item = String>>(mut argv: I) -> Result<Option<Args>, String> {
+    let mut args = Args::default();
+    let mut input = None;
+
+    while let Some(arg) = argv.next() {
+        let mut value = |flag: &str| argv.next().ok_or_else(|| format!("{} requires a value", flag));
+
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "-o" | "--output" => args.output = Some(value(&arg)?.into()),
+            "--stats" => args.stats = Some(value(&arg)?.into()),
+            "--emit-thdl" => args.emit_thdl = Some(value(&arg)?.into()),
+            "--target" => args.options.target = parse_target(&value(&arg)?)?,
+            "--constraints" => args.constraint_files.push(value(&arg)?.into()),
+            "-O0" => {
+                args.options.optimize_area = false;
+                args.options.optimize_speed = false;
+                args.options.optimize_power = false;
+            }
+            "--optimize-area" => args.options.optimize_area = true,
+            "--no-optimize-area" => args.options.optimize_area = false,
+            "--optimize-speed" => args.options.optimize_speed = true,
+            "--no-optimize-speed" => args.options.optimize_speed = false,
+            "--optimize-power" => args.options.optimize_power = true,
+            "--no-optimize-power" => args.options.optimize_power = false,
+            flag if flag.starts_with('-') => return Err(format!("unknown option '{}'", flag)),
+            path if input.is_none() => input = Some(PathBuf::from(path)),
+            extra => return Err(format!("unexpected argument '{}'", extra)),
+        }
+    }
+
+    args.input = input.ok_or("no input file")?;
+    Ok(Some(args))
+}
+
+fn source_kind(path: &Path) -> Result<SourceKind, String> {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("tsl") => Ok(SourceKind::Tsl),
+        Some("thdl") => Ok(SourceKind::Thdl),
+        _ => Err(format!("{}: expected a .tsl or .thdl file", path.display())),
+    }
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Apply the keys set in one constraint file on top of `constraints`
+fn layer_constraints(constraints: &mut TimingConstraints, path: &Path, text: &str) -> Result<(), String> {
+    let set = project::parse_constraint_set(text).map_err(|e| format!("{}: {:?}", path.display(), e))?;
+    set.apply_to(constraints);
+    Ok(())
+}
+
+/// Warning for THDL whose parsed form has no ports or logic
+fn empty_netlist_warning(thdl: &str) -> Option<String> {
+    let module = plenumnet_thdl::ir::parse(thdl).ok()?;
+    module.is_empty().then(|| format!(
+        "netlist for module '{}' has no ports or logic (THDL text parsing currently reads only the module header); use --emit-thdl for the generated THDL",
+        module.name
+    ))
+}
+
+/// Statistics report as JSON
+fn stats_json(input: &Path, options: &SynthesisOptions, stats: &SynthesisStats) -> String {
+    format!(
+        "{{\n  \"input\": {},\n  \"target\": \"{:?}\",\n  \"trit_cells\": {},\n  \"gates\": {},\n  \"flip_flops\": {},\n  \"estimated_area_um2\": {:.3},\n  \"estimated_power_mw\": {:.3},\n  \"critical_path_ps\": {},\n  \"max_clock_period_ps\": {}\n}}\n",
+        json_string(&input.display().to_string()),
+        options.target,
+        stats.trit_cells,
+        stats.gates,
+        stats.flip_flops,
+        stats.estimated_area_um2,
+        stats.estimated_power_mw,
+        stats.critical_path_ps,
+        options.timing_constraints.max_clock_period_ps,
+    )
+}
+
+fn run(mut args: Args) -> Result<(), String> {
+    let kind = source_kind(&args.input)?;
+    let source = read(&args.input)?;
+
+    for file in &args.constraint_files {
+        layer_constraints(&mut args.options.timing_constraints, file, &read(file)?)?;
+    }
+
+    let thdl = match kind {
+        SourceKind::Tsl => plenumnet_tsl::compile_to_thdl(&source)
+            .map_err(|e| format!("{}: {:?}", args.input.display(), e))?,
+        SourceKind::Thdl => source.clone(),
+    };
+    if let Some(ref path) = args.emit_thdl {
+        write(path, &thdl)?;
+    }
+
+    let SynthesisResult { output, statistics } = plenumnet_thdl::synthesize(&thdl, &args.options)
+        .map_err(|e| format!("{}: {:?}", args.input.display(), e))?;
+
+    let warnings = project::lint_source(kind, &source).map_err(|e| format!("{}: {}", args.input.display(), e))?;
+    for message in warnings {
+        let level = match message.level {
+            LintLevel::Warning => "warning",
+            LintLevel::Error => "error",
+        };
+        eprintln!("ternaryc: {}: {}: {}", args.input.display(), level, message.message);
+    }
+    if let Some(warning) = empty_netlist_warning(&thdl) {
+        eprintln!("ternaryc: {}: warning: {}", args.input.display(), warning);
+    }
+
+    let output_path = args.output.clone().unwrap_or_else(|| args.input.with_extension("v"));
+    let stats_path = args.stats.clone().unwrap_or_else(|| args.input.with_extension("stats.json"));
+    write(&output_path, &output)?;
+    write(&stats_path, &stats_json(&args.input, &args.options, &statistics))?;
+
+    eprintln!(
+        "ternaryc: {} -> {} ({} trit cells, {} gates, {} flip-flops, critical path {} ps)",
+        args.input.display(),
+        output_path.display(),
+        statistics.trit_cells,
+        statistics.gates,
+        statistics.flip_flops,
+        statistics.critical_path_ps,
+    );
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("ternaryc: {}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("ternaryc: error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(argv: &[&str]) -> Result<Option<Args>, String> {
+        parse_args(argv.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = parse(&["alu.tsl", "--target", "asic", "-O0", "--optimize-power", "-o", "alu.v"])
+            .unwrap()
+            .unwrap();
+        assert_eq!(args.input, PathBuf::from("alu.tsl"));
+        assert_eq!(args.output, Some(PathBuf::from("alu.v")));
+        assert_eq!(args.options.target, Target::Asic);
+        assert!(!args.options.optimize_area && !args.options.optimize_speed && args.options.optimize_power);
+
+        assert!(parse(&["--help"]).unwrap().is_none());
+        assert!(parse(&["alu.tsl", "--target", "gpu"]).is_err());
+        assert!(parse(&["alu.tsl", "--stats"]).is_err());
+        assert!(parse(&[]).is_err());
+    }
+
+    #[test]
+    fn test_constraint_files_layer_per_key() {
+        let mut constraints = TimingConstraints::default();
+        layer_constraints(&mut constraints, Path::new("clk.txt"), "max_clock_period_ps = 1000").unwrap();
+        layer_constraints(&mut constraints, Path::new("m.txt"), "setup_margin_ps = 50").unwrap();
+        assert_eq!(constraints.max_clock_period_ps, 1000);
+        assert_eq!(constraints.setup_margin_ps, 50);
+        assert!(layer_constraints(&mut constraints, Path::new("bad.txt"), "bogus = 1").is_err());
+    }
+
+    #[test]
+    fn test_empty_netlist_warning() {
+        let thdl = plenumnet_tsl::compile_to_thdl("fsm ctl(clk, rst) {\n    state a, b;\n    reset a;\n    a -> b;\n}\n").unwrap();
+        assert!(empty_netlist_warning(&thdl).unwrap().contains("module 'ctl'"));
+    }
+
+    #[test]
+    fn test_stats_report_is_json() {
+        let json = stats_json(Path::new("a\u{1b}\"b.tsl"), &SynthesisOptions::default(), &SynthesisStats::default());
+        assert!(json.contains("\"input\": \"a\\u001b\\\"b.tsl\","));
+    }
+}
@@ -2,6 +2,8 @@
 
 use crate::ast::*;
 use crate::CompileError;
+use plenumnet_thdl::ir as thdl;
+use plenumnet_thdl::lint::LintMessage;
 
 /// Generate THDL code from TSL AST
 pub fn generate_thdl(program: &Program) -> Result<String, CompileError> {
@@ -15,9 +17,201 @@ pub fn generate_thdl(program: &Program) -> Result<String, CompileError> {
         output.push_str("\n\n");
     }
     
+    for sm in &program.state_machines {
+        output.push_str(&generate_state_machine(sm)?);
+        output.push_str("\n\n");
+    }
+    
     Ok(output)
 }
 
+/// Output port carrying the current state encoding of a generated state machine
+const STATE_PORT: &str = "state";
+
+/// Run the THDL lint checks on the hardware generated for a program
+pub fn lint(program: &Program) -> Result<Vec<LintMessage>, CompileError> {
+    let mut messages = Vec::new();
+    for sm in &program.state_machines {
+        messages.extend(plenumnet_thdl::lint::lint(&state_machine_module(sm)?));
+    }
+    Ok(messages)
+}
+
+/// Lower a state machine through the THDL IR so reset and illegal-state
+/// recovery logic is generated the same way as for native THDL designs
+fn generate_state_machine(sm: &StateMachine) -> Result<String, CompileError> {
+    plenumnet_thdl::synthesizer::module_to_verilog(&state_machine_module(sm)?)
+        .map(|text| text.trim_end().to_string())
+        .map_err(|e| CompileError::CodeGenError(format!("{:?}", e)))
+}
+
+fn state_machine_module(sm: &StateMachine) -> Result<thdl::Module, CompileError> {
+    let mut transitions = Vec::with_capacity(sm.transitions.len());
+    let mut inputs: Vec<String> = Vec::new();
+    
+    for t in &sm.transitions {
+        let condition = match &t.condition {
+            Some(cond) => {
+                collect_idents(cond, &mut inputs);
+                Some(to_thdl_expression(cond)?)
+            }
+            None => None,
+        };
+        transitions.push(thdl::Transition {
+            from: t.from.clone(),
+            to: t.to.clone(),
+            condition,
+        });
+    }
+    inputs.retain(|name| *name != sm.clock && *name != sm.reset);
+    
+    let machine = thdl::StateMachine {
+        name: sm.name.clone(),
+        clock: sm.clock.clone(),
+        reset: sm.reset.clone(),
+        states: sm.states.clone(),
+        reset_state: sm.reset_state.clone(),
+        transitions,
+        encoding: match sm.encoding {
+            StateEncoding::OneHot => thdl::StateEncoding::OneHot,
+            StateEncoding::Gray => thdl::StateEncoding::Gray,
+            StateEncoding::Ternary => thdl::StateEncoding::Ternary,
+        },
+    };
+    check_port_names(sm, &inputs, &machine.state_register())?;
+    
+    let mut ports = vec![
+        input_port(&sm.clock, 1, false),
+        input_port(&sm.reset, 1, false),
+    ];
+    for name in &inputs {
+        ports.push(input_port(name, type_to_thdl_width(&Type::Trit), true));
+    }
+    
+    // Expose the state register so the machine can drive other logic
+    ports.push(thdl::Port {
+        name: STATE_PORT.to_string(),
+        direction: thdl::PortDirection::Output,
+        width: machine.state_width(),
+        trit_type: machine.encoding == thdl::StateEncoding::Ternary,
+    });
+    let assignments = vec![thdl::Assignment {
+        target: STATE_PORT.to_string(),
+        expression: thdl::Expression::Ident(machine.state_register()),
+    }];
+    
+    Ok(thdl::Module {
+        name: sm.name.clone(),
+        ports,
+        signals: Vec::new(),
+        instances: Vec::new(),
+        assignments,
+        always_blocks: Vec::new(),
+        state_machines: vec![machine],
+    })
+}
+
+/// Reject clock/reset/input names that would collide in the generated module
+fn check_port_names(sm: &StateMachine, inputs: &[String], register: &str) -> Result<(), CompileError> {
+    let error = |message: String| Err(CompileError::SemanticError(format!("State machine {}: {}", sm.name, message)));
+    
+    if sm.clock == sm.reset {
+        return error(format!("clock and reset are both '{}'", sm.clock));
+    }
+    let signals = [&sm.clock, &sm.reset].into_iter().chain(inputs);
+    for name in signals {
+        if name == STATE_PORT {
+            return error(format!("'{}' is reserved for the state output", STATE_PORT));
+        }
+        if name == register {
+            return error(format!("'{}' is the state register and cannot be an input", register));
+        }
+    }
+    Ok(())
+}
+
+fn input_port(name: &str, width: usize, trit_type: bool) -> thdl::Port {
+    thdl::Port {
+        name: name.to_string(),
+        direction: thdl::PortDirection::Input,
+        width,
+        trit_type,
+    }
+}
+
+fn collect_idents(expr: &Expression, names: &mut Vec<String>) {
+    match expr {
+        Expression::Ident(name) if !names.contains(name) => names.push(name.clone()),
+        Expression::Binary { left, right, .. } => {
+            collect_idents(left, names);
+            collect_idents(right, names);
+        }
+        Expression::Unary { expr, .. } => collect_idents(expr, names),
+        Expression::Call { args, .. } => {
+            for arg in args {
+                collect_idents(arg, names);
+            }
+        }
+        Expression::Index { array, index } => {
+            collect_idents(array, names);
+            collect_idents(index, names);
+        }
+        _ => {}
+    }
+}
+
+fn to_thdl_expression(expr: &Expression) -> Result<thdl::Expression, CompileError> {
+    Ok(match expr {
+        Expression::TritLiteral(v) => thdl::Expression::TritLiteral(*v),
+        Expression::IntLiteral(v) => thdl::Expression::Literal(*v),
+        Expression::StringLiteral(_) => {
+            return Err(CompileError::SemanticError("String literal in state machine condition".into()))
+        }
+        Expression::Ident(name) => thdl::Expression::Ident(name.clone()),
+        Expression::Binary { left, op, right } => {
+            let l = to_thdl_expression(left)?;
+            let r = to_thdl_expression(right)?;
+            let op = match op {
+                BinaryOp::Add => thdl::BinaryOp::TritAdd,
+                BinaryOp::Mul => thdl::BinaryOp::TritMul,
+                BinaryOp::Xor => thdl::BinaryOp::TritXor,
+                BinaryOp::Eq => thdl::BinaryOp::Eq,
+                BinaryOp::Ne => thdl::BinaryOp::Ne,
+                BinaryOp::Lt => thdl::BinaryOp::Lt,
+                BinaryOp::Gt => thdl::BinaryOp::Gt,
+                BinaryOp::Le => thdl::BinaryOp::Le,
+                BinaryOp::Ge => thdl::BinaryOp::Ge,
+                BinaryOp::Sub => return Ok(thdl::Expression::FunctionCall("trit_sub".into(), vec![l, r])),
+                BinaryOp::Div => return Ok(thdl::Expression::FunctionCall("trit_div".into(), vec![l, r])),
+            };
+            thdl::Expression::BinaryOp(op, Box::new(l), Box::new(r))
+        }
+        Expression::Unary { op, expr } => {
+            let e = to_thdl_expression(expr)?;
+            match op {
+                UnaryOp::Not => thdl::Expression::UnaryOp(thdl::UnaryOp::TritNot, Box::new(e)),
+                UnaryOp::Rotate => thdl::Expression::UnaryOp(thdl::UnaryOp::TritRotate, Box::new(e)),
+                UnaryOp::RotateInv => thdl::Expression::FunctionCall("trit_rotate_inv".into(), vec![e]),
+            }
+        }
+        Expression::Call { name, args } => {
+            let args: Result<Vec<_>, _> = args.iter().map(to_thdl_expression).collect();
+            thdl::Expression::FunctionCall(name.clone(), args?)
+        }
+        Expression::Index { array, index } => match index.as_ref() {
+            Expression::TritLiteral(i) if *i >= 0 => {
+                thdl::Expression::BitSelect(Box::new(to_thdl_expression(array)?), *i as usize)
+            }
+            Expression::IntLiteral(i) if *i >= 0 => {
+                thdl::Expression::BitSelect(Box::new(to_thdl_expression(array)?), *i as usize)
+            }
+            _ => {
+                return Err(CompileError::SemanticError("State machine conditions only support constant indices".into()))
+            }
+        },
+    })
+}
+
 fn generate_function(func: &Function) -> Result<String, CompileError> {
     let mut output = String::new();
     
@@ -178,3 +372,67 @@ fn type_to_thdl_width(ty: &Type) -> usize {
         Type::Array(inner, size) => type_to_thdl_width(inner) * size,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{lexer, parser};
+
+    #[test]
+    fn test_state_machine_generates_recovery() {
+        let source = "fsm ctl(clk, rst): gray {\n    state idle, run, stop;\n    reset idle;\n    idle -> run if go;\n    run -> stop;\n}\n";
+        let program = parser::parse(&lexer::tokenize(source).unwrap()).unwrap();
+        assert_eq!(program.state_machines.len(), 1);
+
+        let thdl = generate_thdl(&program).unwrap();
+        assert!(thdl.contains("module ctl ("));
+        assert!(thdl.contains("input [1:0] go"));
+        assert!(thdl.contains("reg [1:0] ctl_state;"));
+        assert!(thdl.contains("output [1:0] state\n"));
+        assert!(thdl.contains("assign state = ctl_state;"));
+        assert!(thdl.contains("default: begin"));
+    }
+
+    #[test]
+    fn test_state_machine_unknown_state_rejected() {
+        let source = "fsm ctl(clk, rst) {\n    state idle;\n    reset idle;\n    idle -> nowhere;\n}\n";
+        let program = parser::parse(&lexer::tokenize(source).unwrap()).unwrap();
+        assert!(matches!(generate_thdl(&program), Err(CompileError::CodeGenError(_))));
+    }
+
+    fn compile(source: &str) -> Result<String, CompileError> {
+        generate_thdl(&parser::parse(&lexer::tokenize(source).unwrap()).unwrap())
+    }
+
+    #[test]
+    fn test_state_machine_clock_named_state_rejected() {
+        let result = compile("fsm ctl(state, rst) {\n    state a, b;\n    reset a;\n    a -> b;\n}\n");
+        assert!(matches!(result, Err(CompileError::SemanticError(_))));
+    }
+
+    #[test]
+    fn test_state_machine_clock_as_reset_rejected() {
+        let result = compile("fsm ctl(clk, clk) {\n    state a, b;\n    reset a;\n    a -> b;\n}\n");
+        assert!(matches!(result, Err(CompileError::SemanticError(_))));
+    }
+
+    #[test]
+    fn test_state_machine_condition_on_register_rejected() {
+        let result = compile("fsm ctl(clk, rst) {\n    state a, b;\n    reset a;\n    a -> b if ctl_state;\n}\n");
+        assert!(matches!(result, Err(CompileError::SemanticError(_))));
+    }
+
+    #[test]
+    fn test_state_machine_duplicate_reset_rejected() {
+        let source = "fsm ctl(clk, rst) {\n    state a, b;\n    reset a;\n    reset b;\n}\n";
+        let result = parser::parse(&lexer::tokenize(source).unwrap());
+        assert!(matches!(result, Err(CompileError::ParserError(m)) if m.contains("more than one reset state")));
+    }
+
+    #[test]
+    fn test_fsm_is_contextual_keyword() {
+        let program = parser::parse(&lexer::tokenize("fn f(fsm: trit) -> trit { return fsm; }").unwrap()).unwrap();
+        assert_eq!(program.functions[0].params[0].name, "fsm");
+        assert!(program.state_machines.is_empty());
+    }
+}
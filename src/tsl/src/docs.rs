@@ -0,0 +1,126 @@
+//! TSL Documentation Extraction
+//!
+//! Collects `//!` module docs and `///` doc comments attached to function
+//! definitions, using the scanner and JSON shape shared with THDL in
+//! [`plenumnet_thdl::docs`].
+
+use crate::ast::{Function, Type};
+use crate::{lexer, parser, CompileError};
+use plenumnet_thdl::docs::{scan, DocItem, DocSyntax};
+
+pub use plenumnet_thdl::docs::{DocSymbol, SourceDocs, SourceSpan, SymbolKind};
+
+const TSL_SYNTAX: DocSyntax = DocSyntax {
+    language: "tsl",
+    version: crate::TSL_VERSION,
+    keyword: "fn",
+    kind: SymbolKind::Function,
+};
+
+/// Extract documentation from TSL source
+pub fn extract(source: &str) -> Result<SourceDocs, CompileError> {
+    scan(source, &TSL_SYNTAX, function_item)
+}
+
+fn function_item(source: &str, start: usize) -> Result<DocItem, CompileError> {
+    let open = source[start..].find('{')
+        .map(|i| start + i)
+        .ok_or_else(|| CompileError::ParserError("Expected function body".into()))?;
+    let close = matching_brace(source, open)
+        .ok_or_else(|| CompileError::ParserError("Unterminated function body".into()))?;
+
+    // Reuse the real parser on the header with an empty body
+    let header = format!("{} {{}}", &source[start..open]);
+    let tokens = lexer::tokenize(&header)?;
+    let program = parser::parse(&tokens)?;
+    let function = program.functions.into_iter().next()
+        .ok_or_else(|| CompileError::ParserError("Expected function".into()))?;
+
+    Ok(DocItem {
+        name: function.name.clone(),
+        signature: render_signature(&function),
+        end: close + 1,
+    })
+}
+
+/// Find the `}` matching the `{` at `open`, skipping strings and line comments
+fn matching_brace(source: &str, open: usize) -> Option<usize> {
+    let bytes = source.as_bytes();
+    let mut depth = 0usize;
+    let mut i = open;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            b'"' => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    None
+}
+
+fn render_signature(function: &Function) -> String {
+    let params: Vec<String> = function.params.iter()
+        .map(|p| format!("{}: {}", p.name, type_name(&p.ty)))
+        .collect();
+    let mut signature = format!("fn {}({})", function.name, params.join(", "));
+    if let Some(ref ret) = function.return_type {
+        signature.push_str(&format!(" -> {}", type_name(ret)));
+    }
+    signature
+}
+
+fn type_name(ty: &Type) -> String {
+    match ty {
+        Type::Trit => "trit".to_string(),
+        Type::Tryte => "tryte".to_string(),
+        Type::Word => "word".to_string(),
+        Type::Array(inner, size) => format!("[{}; {}]", type_name(inner), size),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "//! Trit helpers\n\n/// Add two trits\n/// in GF(3)\nfn add(a: trit, b: trit) -> trit {\n    return a + b;\n}\n\nfn undocumented(x: trit) {\n}\n";
+
+    #[test]
+    fn test_extracts_documented_function() {
+        let docs = extract(SOURCE).unwrap();
+        assert_eq!(docs.module_doc, "Trit helpers");
+        assert_eq!(docs.symbols.len(), 2);
+
+        let add = &docs.symbols[0];
+        assert_eq!(add.signature, "fn add(a: trit, b: trit) -> trit");
+        assert_eq!(add.docs, "Add two trits\nin GF(3)");
+        assert_eq!((add.span.start_line, add.span.end_line), (5, 7));
+        assert!(docs.symbols[1].docs.is_empty());
+    }
+
+    #[test]
+    fn test_json_escapes_docs() {
+        let docs = extract("/// Says \"hi\"\nfn hi() {}\n").unwrap();
+        let json = docs.to_json();
+        assert!(json.contains("\"docs\":\"Says \\\"hi\\\"\""));
+        assert!(json.starts_with("{\"language\":\"tsl\""));
+    }
+}
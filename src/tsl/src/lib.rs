@@ -11,6 +11,8 @@ pub mod lexer;
 pub mod parser;
 pub mod ast;
 pub mod compiler;
+pub mod docs;
+pub mod project;
 
 /// TSL version
 pub const TSL_VERSION: &str = "0.1.0";
@@ -22,6 +24,13 @@ pub fn compile_to_thdl(source: &str) -> Result<String, CompileError> {
     compiler::generate_thdl(&ast)
 }
 
+/// Run the THDL lint checks (e.g. unreachable states) on TSL source
+pub fn lint(source: &str) -> Result<Vec<plenumnet_thdl::lint::LintMessage>, CompileError> {
+    let tokens = lexer::tokenize(source)?;
+    let ast = parser::parse(&tokens)?;
+    compiler::lint(&ast)
+}
+
 /// Compilation error
 #[derive(Debug, Clone)]
 pub enum CompileError {
@@ -40,12 +40,18 @@ impl<'a> Parser<'a> {
     
     fn parse_program(&mut self) -> Result<Program, CompileError> {
         let mut functions = Vec::new();
+        let mut state_machines = Vec::new();
         
         while *self.current() != Token::Eof {
-            functions.push(self.parse_function()?);
+            // `fsm` is only a keyword at item level, so it stays usable as a name
+            if matches!(self.current(), Token::Ident(s) if s == "fsm") {
+                state_machines.push(self.parse_fsm()?);
+            } else {
+                functions.push(self.parse_function()?);
+            }
         }
         
-        Ok(Program { functions })
+        Ok(Program { functions, state_machines })
     }
     
     fn parse_function(&mut self) -> Result<Function, CompileError> {
@@ -75,6 +81,88 @@ impl<'a> Parser<'a> {
         Ok(Function { name, params, return_type, body })
     }
     
+    fn expect_ident(&mut self, what: &str) -> Result<String, CompileError> {
+        match self.current() {
+            Token::Ident(s) => {
+                let name = s.clone();
+                self.advance();
+                Ok(name)
+            }
+            _ => Err(CompileError::ParserError(format!("Expected {}", what))),
+        }
+    }
+    
+    fn parse_fsm(&mut self) -> Result<StateMachine, CompileError> {
+        self.advance(); // consume 'fsm'
+        
+        let name = self.expect_ident("state machine name")?;
+        self.expect(Token::LParen)?;
+        let clock = self.expect_ident("clock signal")?;
+        self.expect(Token::Comma)?;
+        let reset = self.expect_ident("reset signal")?;
+        self.expect(Token::RParen)?;
+        
+        // Ternary encoding suits trit-cell registers, so it is the default
+        let encoding = if *self.current() == Token::Colon {
+            self.advance();
+            match self.expect_ident("state encoding")?.as_str() {
+                "onehot" => StateEncoding::OneHot,
+                "gray" => StateEncoding::Gray,
+                "ternary" => StateEncoding::Ternary,
+                other => return Err(CompileError::ParserError(format!("Unknown state encoding: {}", other))),
+            }
+        } else {
+            StateEncoding::Ternary
+        };
+        
+        self.expect(Token::LBrace)?;
+        
+        let mut states = Vec::new();
+        let mut reset_state = None;
+        let mut transitions = Vec::new();
+        
+        while *self.current() != Token::RBrace && *self.current() != Token::Eof {
+            let word = self.expect_ident("state, reset or transition")?;
+            
+            if word == "state" && matches!(self.current(), Token::Ident(_)) {
+                loop {
+                    states.push(self.expect_ident("state name")?);
+                    if *self.current() != Token::Comma {
+                        break;
+                    }
+                    self.advance();
+                }
+            } else if word == "reset" && matches!(self.current(), Token::Ident(_)) {
+                if reset_state.is_some() {
+                    return Err(CompileError::ParserError(format!(
+                        "State machine {} declares more than one reset state", name
+                    )));
+                }
+                reset_state = Some(self.expect_ident("reset state")?);
+            } else {
+                self.expect(Token::Arrow)?;
+                let to = self.expect_ident("target state")?;
+                let condition = if *self.current() == Token::If {
+                    self.advance();
+                    Some(self.parse_expression()?)
+                } else {
+                    None
+                };
+                transitions.push(Transition { from: word, to, condition });
+            }
+            
+            self.expect(Token::Semicolon)?;
+        }
+        
+        self.expect(Token::RBrace)?;
+        
+        let reset_state = reset_state.ok_or_else(|| {
+            CompileError::ParserError(format!("State machine {} has no reset state", name))
+        })?;
+        
+        Ok(StateMachine { name, clock, reset, encoding, states, reset_state, transitions })
+    }
+    
     fn parse_params(&mut self) -> Result<Vec<Parameter>, CompileError> {
         let mut params = Vec::new();
         
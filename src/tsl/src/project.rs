@@ -0,0 +1,520 @@
+//! Multi-module TSL/THDL Project Builds
+//!
+//! A [`Project`] declares TSL and THDL modules, their dependencies,
+//! constraint files and synthesis options. [`build`] orders the modules
+//! by dependency, compiles TSL to THDL, synthesizes every module and
+//! returns a consolidated [`BuildReport`]. Results are kept in a
+//! [`BuildCache`] so unchanged modules are reused on the next build.
+
+use plenumnet_thdl::lint::{self, LintMessage};
+use plenumnet_thdl::{SynthesisOptions, SynthesisResult, SynthesisStats, TimingConstraints};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+/// Source language of a project module
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SourceKind {
+    Tsl,
+    Thdl,
+}
+
+/// A module declared in a project
+#[derive(Debug, Clone)]
+pub struct ModuleSpec {
+    pub name: String,
+    pub kind: SourceKind,
+    pub source: String,
+    pub dependencies: Vec<String>,
+}
+
+/// Timing constraints applied to some (or all) modules
+#[derive(Debug, Clone)]
+pub struct ConstraintFile {
+    pub name: String,
+    /// Modules the constraints apply to; empty means every module
+    pub applies_to: Vec<String>,
+    pub constraints: ConstraintSet,
+}
+
+/// Constraint values set by one file; unset keys keep their earlier value
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConstraintSet {
+    pub max_clock_period_ps: Option<u64>,
+    pub setup_margin_ps: Option<u64>,
+    pub hold_margin_ps: Option<u64>,
+}
+
+impl ConstraintSet {
+    /// Layer the keys this set defines onto `constraints`
+    pub fn apply_to(&self, constraints: &mut TimingConstraints) {
+        if let Some(v) = self.max_clock_period_ps {
+            constraints.max_clock_period_ps = v;
+        }
+        if let Some(v) = self.setup_margin_ps {
+            constraints.setup_margin_ps = v;
+        }
+        if let Some(v) = self.hold_margin_ps {
+            constraints.hold_margin_ps = v;
+        }
+    }
+}
+
+/// Project description
+#[derive(Debug, Clone, Default)]
+pub struct Project {
+    pub name: String,
+    pub modules: Vec<ModuleSpec>,
+    pub constraint_files: Vec<ConstraintFile>,
+    pub options: SynthesisOptions,
+}
+
+/// Project-level build error (problems with the graph itself)
+#[derive(Debug, Clone, PartialEq)]
+pub enum BuildError {
+    DuplicateModule(String),
+    UnknownDependency { module: String, dependency: String },
+    DependencyCycle(Vec<String>),
+    ConstraintError(String),
+}
+
+/// Outcome of a single build step
+#[derive(Debug, Clone)]
+pub enum StepStatus {
+    Built,
+    Cached,
+    Failed(String),
+    /// Not attempted because a dependency failed
+    Skipped { failed_dependency: String },
+}
+
+/// Report for one module
+#[derive(Debug, Clone)]
+pub struct StepReport {
+    pub module: String,
+    pub kind: SourceKind,
+    pub status: StepStatus,
+    pub statistics: Option<SynthesisStats>,
+    /// Lint findings for built or cached modules
+    pub warnings: Vec<LintMessage>,
+}
+
+/// Consolidated build report
+#[derive(Debug, Clone, Default)]
+pub struct BuildReport {
+    pub project: String,
+    /// Steps in execution order
+    pub steps: Vec<StepReport>,
+    pub total: SynthesisStats,
+}
+
+impl BuildReport {
+    pub fn success(&self) -> bool {
+        self.steps.iter().all(|s| matches!(s.status, StepStatus::Built | StepStatus::Cached))
+    }
+
+    pub fn built_count(&self) -> usize {
+        self.steps.iter().filter(|s| matches!(s.status, StepStatus::Built)).count()
+    }
+
+    pub fn cached_count(&self) -> usize {
+        self.steps.iter().filter(|s| matches!(s.status, StepStatus::Cached)).count()
+    }
+
+    pub fn warning_count(&self) -> usize {
+        self.steps.iter().map(|s| s.warnings.len()).sum()
+    }
+}
+
+/// Artifacts produced for one module
+#[derive(Debug, Clone)]
+pub struct ModuleArtifact {
+    /// THDL source (generated for TSL modules)
+    pub thdl: String,
+    pub result: SynthesisResult,
+    pub warnings: Vec<LintMessage>,
+}
+
+/// Incremental build cache, keyed by module name
+#[derive(Debug, Clone, Default)]
+pub struct BuildCache {
+    entries: HashMap<String, (u64, ModuleArtifact)>,
+}
+
+impl BuildCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, module: &str) -> Option<&ModuleArtifact> {
+        self.entries.get(module).map(|(_, artifact)| artifact)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// Parse a constraint file body
+///
+/// One `key = value` per line, values in picoseconds; `//` starts a comment.
+/// Recognized keys: `max_clock_period_ps`, `setup_margin_ps`, `hold_margin_ps`.
+/// Keys missing from the file are left at their defaults.
+pub fn parse_constraints(text: &str) -> Result<TimingConstraints, BuildError> {
+    let mut constraints = TimingConstraints::default();
+    parse_constraint_set(text)?.apply_to(&mut constraints);
+    Ok(constraints)
+}
+
+/// Parse a constraint file body, keeping track of which keys it sets
+pub fn parse_constraint_set(text: &str) -> Result<ConstraintSet, BuildError> {
+    let mut constraints = ConstraintSet::default();
+
+    for (i, raw) in text.lines().enumerate() {
+        let line = raw.split("//").next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let (key, value) = line.split_once('=')
+            .ok_or_else(|| BuildError::ConstraintError(format!("line {}: expected key = value", i + 1)))?;
+        let value: u64 = value.trim().parse()
+            .map_err(|_| BuildError::ConstraintError(format!("line {}: invalid value", i + 1)))?;
+
+        match key.trim() {
+            "max_clock_period_ps" => constraints.max_clock_period_ps = Some(value),
+            "setup_margin_ps" => constraints.setup_margin_ps = Some(value),
+            "hold_margin_ps" => constraints.hold_margin_ps = Some(value),
+            other => {
+                return Err(BuildError::ConstraintError(format!(
+                    "line {}: unknown constraint '{}'", i + 1, other
+                )))
+            }
+        }
+    }
+
+    Ok(constraints)
+}
+
+/// Compute the build order (dependencies first, declaration order otherwise)
+pub fn build_order(project: &Project) -> Result<Vec<String>, BuildError> {
+    let mut index = HashMap::new();
+    for (i, module) in project.modules.iter().enumerate() {
+        if index.insert(module.name.as_str(), i).is_some() {
+            return Err(BuildError::DuplicateModule(module.name.clone()));
+        }
+    }
+
+    for module in &project.modules {
+        for dep in &module.dependencies {
+            if !index.contains_key(dep.as_str()) {
+                return Err(BuildError::UnknownDependency {
+                    module: module.name.clone(),
+                    dependency: dep.clone(),
+                });
+            }
+        }
+    }
+
+    for file in &project.constraint_files {
+        for target in &file.applies_to {
+            if !index.contains_key(target.as_str()) {
+                return Err(BuildError::ConstraintError(format!(
+                    "{}: unknown module '{}'", file.name, target
+                )));
+            }
+        }
+    }
+
+    // Depth-first topological sort; the stack doubles as cycle reporting
+    let mut order = Vec::with_capacity(project.modules.len());
+    let mut done = HashSet::new();
+    let mut stack = Vec::new();
+
+    fn visit<'a>(
+        project: &'a Project,
+        index: &HashMap<&str, usize>,
+        name: &'a str,
+        done: &mut HashSet<&'a str>,
+        stack: &mut Vec<&'a str>,
+        order: &mut Vec<String>,
+    ) -> Result<(), BuildError> {
+        if done.contains(name) {
+            return Ok(());
+        }
+        if let Some(pos) = stack.iter().position(|n| *n == name) {
+            let mut cycle: Vec<String> = stack[pos..].iter().map(|s| s.to_string()).collect();
+            cycle.push(name.to_string());
+            return Err(BuildError::DependencyCycle(cycle));
+        }
+
+        stack.push(name);
+        for dep in &project.modules[index[name]].dependencies {
+            visit(project, index, dep, done, stack, order)?;
+        }
+        stack.pop();
+
+        done.insert(name);
+        order.push(name.to_string());
+        Ok(())
+    }
+
+    for module in &project.modules {
+        visit(project, &index, &module.name, &mut done, &mut stack, &mut order)?;
+    }
+
+    Ok(order)
+}
+
+/// Build every module in the project, reusing `cache` where possible
+pub fn build(project: &Project, cache: &mut BuildCache) -> Result<BuildReport, BuildError> {
+    let order = build_order(project)?;
+    let modules: HashMap<&str, &ModuleSpec> =
+        project.modules.iter().map(|m| (m.name.as_str(), m)).collect();
+
+    let mut report = BuildReport {
+        project: project.name.clone(),
+        ..Default::default()
+    };
+    let mut fingerprints: HashMap<&str, u64> = HashMap::new();
+    let mut failed: HashSet<&str> = HashSet::new();
+
+    for name in &order {
+        let module = modules[name.as_str()];
+
+        if let Some(dep) = module.dependencies.iter().find(|d| failed.contains(d.as_str())) {
+            failed.insert(module.name.as_str());
+            report.steps.push(StepReport {
+                module: module.name.clone(),
+                kind: module.kind,
+                status: StepStatus::Skipped { failed_dependency: dep.clone() },
+                statistics: None,
+                warnings: Vec::new(),
+            });
+            continue;
+        }
+
+        let options = module_options(project, &module.name);
+        let fingerprint = fingerprint(module, &options, &fingerprints);
+        fingerprints.insert(module.name.as_str(), fingerprint);
+
+        let cached = cache.entries.get(&module.name)
+            .filter(|(fp, _)| *fp == fingerprint)
+            .map(|(_, artifact)| (artifact.result.statistics.clone(), artifact.warnings.clone()));
+
+        let (status, statistics, warnings) = match cached {
+            Some((stats, warnings)) => (StepStatus::Cached, Some(stats), warnings),
+            None => match build_module(module, &options) {
+                Ok(artifact) => {
+                    let stats = artifact.result.statistics.clone();
+                    let warnings = artifact.warnings.clone();
+                    cache.entries.insert(module.name.clone(), (fingerprint, artifact));
+                    (StepStatus::Built, Some(stats), warnings)
+                }
+                Err(message) => {
+                    failed.insert(module.name.as_str());
+                    cache.entries.remove(&module.name);
+                    (StepStatus::Failed(message), None, Vec::new())
+                }
+            },
+        };
+
+        if let Some(ref stats) = statistics {
+            accumulate(&mut report.total, stats);
+        }
+        report.steps.push(StepReport {
+            module: module.name.clone(),
+            kind: module.kind,
+            status,
+            statistics,
+            warnings,
+        });
+    }
+
+    Ok(report)
+}
+
+fn build_module(module: &ModuleSpec, options: &SynthesisOptions) -> Result<ModuleArtifact, String> {
+    let thdl = match module.kind {
+        SourceKind::Tsl => crate::compile_to_thdl(&module.source)
+            .map_err(|e| format!("{:?}", e))?,
+        SourceKind::Thdl => module.source.clone(),
+    };
+    let result = plenumnet_thdl::synthesize(&thdl, options)
+        .map_err(|e| format!("{:?}", e))?;
+    let warnings = lint_source(module.kind, &module.source)?;
+    Ok(ModuleArtifact { thdl, result, warnings })
+}
+
+/// Lint checks for a TSL or THDL source
+pub fn lint_source(kind: SourceKind, source: &str) -> Result<Vec<LintMessage>, String> {
+    match kind {
+        SourceKind::Tsl => crate::lint(source).map_err(|e| format!("{:?}", e)),
+        SourceKind::Thdl => plenumnet_thdl::ir::parse(source)
+            .map(|module| lint::lint(&module))
+            .map_err(|e| format!("{:?}", e)),
+    }
+}
+
+/// Project options with applicable constraint files layered on key by key
+/// (later files win for the keys they set)
+fn module_options(project: &Project, module: &str) -> SynthesisOptions {
+    let mut options = project.options.clone();
+    for file in &project.constraint_files {
+        if file.applies_to.is_empty() || file.applies_to.iter().any(|m| m == module) {
+            file.constraints.apply_to(&mut options.timing_constraints);
+        }
+    }
+    options
+}
+
+fn fingerprint(module: &ModuleSpec, options: &SynthesisOptions, deps: &HashMap<&str, u64>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    module.kind.hash(&mut hasher);
+    module.source.hash(&mut hasher);
+    format!("{:?}", options).hash(&mut hasher);
+    for dep in &module.dependencies {
+        deps.get(dep.as_str()).hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+fn accumulate(total: &mut SynthesisStats, stats: &SynthesisStats) {
+    total.trit_cells += stats.trit_cells;
+    total.gates += stats.gates;
+    total.flip_flops += stats.flip_flops;
+    total.estimated_area_um2 += stats.estimated_area_um2;
+    total.estimated_power_mw += stats.estimated_power_mw;
+    total.critical_path_ps = total.critical_path_ps.max(stats.critical_path_ps);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn module(name: &str, kind: SourceKind, source: &str, deps: &[&str]) -> ModuleSpec {
+        ModuleSpec {
+            name: name.to_string(),
+            kind,
+            source: source.to_string(),
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+        }
+    }
+
+    fn project() -> Project {
+        Project {
+            name: "demo".to_string(),
+            modules: vec![
+                module("top", SourceKind::Thdl, "module top ();\nendmodule\n", &["adder"]),
+                module("adder", SourceKind::Tsl, "fn adder(a: trit, b: trit) -> trit { return a + b; }", &[]),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_dependencies_build_first() {
+        assert_eq!(build_order(&project()).unwrap(), vec!["adder", "top"]);
+    }
+
+    #[test]
+    fn test_cycle_detected() {
+        let mut p = project();
+        p.modules[1].dependencies.push("top".to_string());
+        assert!(matches!(build_order(&p), Err(BuildError::DependencyCycle(_))));
+    }
+
+    #[test]
+    fn test_rebuild_uses_cache() {
+        let mut p = project();
+        let mut cache = BuildCache::new();
+
+        let first = build(&p, &mut cache).unwrap();
+        assert!(first.success());
+        assert_eq!(first.built_count(), 2);
+
+        let second = build(&p, &mut cache).unwrap();
+        assert_eq!(second.cached_count(), 2);
+
+        // Changing a dependency invalidates its dependents too
+        p.modules[1].source = "fn adder(a: trit, b: trit) -> trit { return a * b; }".to_string();
+        let third = build(&p, &mut cache).unwrap();
+        assert_eq!(third.built_count(), 2);
+    }
+
+    #[test]
+    fn test_parse_constraints() {
+        let c = parse_constraints("// clocks\nmax_clock_period_ps = 1000\nsetup_margin_ps=50\n").unwrap();
+        assert_eq!(c.max_clock_period_ps, 1000);
+        assert_eq!(c.setup_margin_ps, 50);
+        assert!(parse_constraints("bogus = 1").is_err());
+    }
+
+    #[test]
+    fn test_lint_warnings_reported() {
+        let mut p = project();
+        p.modules.push(module(
+            "ctl",
+            SourceKind::Tsl,
+            "fsm ctl(clk, rst) {\n    state idle, run, lost;\n    reset idle;\n    idle -> run;\n}\n",
+            &[],
+        ));
+        let mut cache = BuildCache::new();
+
+        let first = build(&p, &mut cache).unwrap();
+        assert!(first.success());
+        assert_eq!(first.warning_count(), 1);
+        let ctl = first.steps.iter().find(|s| s.module == "ctl").unwrap();
+        assert!(ctl.warnings[0].message.contains("'lost' is unreachable"));
+
+        // Cached steps keep their warnings
+        assert_eq!(build(&p, &mut cache).unwrap().warning_count(), 1);
+    }
+
+    #[test]
+    fn test_constraint_files_layer_per_key() {
+        let mut p = project();
+        p.constraint_files = vec![
+            ConstraintFile {
+                name: "clocks".to_string(),
+                applies_to: vec![],
+                constraints: parse_constraint_set("max_clock_period_ps = 1000\nsetup_margin_ps = 20").unwrap(),
+            },
+            ConstraintFile {
+                name: "adder".to_string(),
+                applies_to: vec!["adder".to_string()],
+                constraints: parse_constraint_set("setup_margin_ps = 50").unwrap(),
+            },
+        ];
+
+        let adder = module_options(&p, "adder").timing_constraints;
+        assert_eq!(adder.max_clock_period_ps, 1000);
+        assert_eq!(adder.setup_margin_ps, 50);
+
+        let top = module_options(&p, "top").timing_constraints;
+        assert_eq!(top.max_clock_period_ps, 1000);
+        assert_eq!(top.setup_margin_ps, 20);
+    }
+
+    #[test]
+    fn test_margins_exceeding_period_fail_build() {
+        let mut p = project();
+        p.constraint_files = vec![ConstraintFile {
+            name: "clocks".to_string(),
+            applies_to: vec![],
+            constraints: parse_constraint_set("max_clock_period_ps = 100\nsetup_margin_ps = 60\nhold_margin_ps = 60").unwrap(),
+        }];
+
+        let report = build(&p, &mut BuildCache::new()).unwrap();
+        assert!(!report.success());
+        assert!(matches!(&report.steps[0].status, StepStatus::Failed(m) if m.contains("TimingViolation")));
+    }
+}